@@ -1,57 +1,282 @@
 
 use crate::lexer::*;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct Ast{
 	pub statements :Vec<Statement>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum AstKind {
 	SelectKind,
 	CreateTableKind,
     InsertKind,
+    UpdateKind,
+    DeleteKind,
+    DropTableKind,
+    AlterTableKind,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct Statement{
-    pub select_statement: 		Option<SelectStatement>,
+    pub select_statement: 		Option<QueryStatement>,
     pub create_table_statement: 	Option<CreateTableStatement>,
     pub insert_statement: 		Option<InsertStatement>,
+    pub update_statement: 		Option<UpdateStatement>,
+    pub delete_statement: 		Option<DeleteStatement>,
+    pub drop_table_statement: 	Option<DropTableStatement>,
+    pub alter_table_statement: 	Option<AlterTableStatement>,
     pub kind: 					AstKind
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct InsertStatement{
+    pub table:   Token,
+    pub columns: Option<Vec<Token>>,
+    pub values:  Vec<Vec<Expression>>
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Assignment{
+    pub column: Token,
+    pub value:  Expression
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct UpdateStatement{
+    pub table:  Token,
+    pub set:    Vec<Assignment>,
+    pub filter: Option<Expression>
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DeleteStatement{
     pub table:  Token,
-    pub values: Vec<Expression>
+    pub filter: Option<Expression>
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum ExpressionKind{
 	LiteralKind,
+	BinaryKind,
+	UnaryKind,
+	FunctionKind,
+	SubqueryKind,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct BinaryExpression{
+    pub a:  Box<Expression>,
+    pub b:  Box<Expression>,
+    pub op: Token
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct UnaryExpression{
+    pub operand: Box<Expression>,
+    pub op:      Token
+}
+
+/// A function call such as `count(*)`, where `asterisk` is set instead of
+/// `args`. A window function call has an `over` specification.
+#[derive(PartialEq, Debug, Clone)]
+pub struct FunctionExpression{
+    pub name:     Token,
+    pub distinct: bool,
+    pub args:     Vec<Expression>,
+    pub asterisk: bool,
+    pub over:     Option<WindowSpecification>
 }
 
-#[derive(PartialEq, Debug)]
+/// The `OVER (PARTITION BY ... ORDER BY ... frame)` of a window function
+/// call, where every part is optional.
+#[derive(PartialEq, Debug, Clone)]
+pub struct WindowSpecification{
+    pub partition_by: Vec<Expression>,
+    pub order_by:     Vec<OrderByItem>,
+    pub frame:        Option<WindowFrame>
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum FrameUnits{
+    RowsUnits,
+    RangeUnits,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum FrameBoundKind{
+    UnboundedPrecedingKind,
+    PrecedingKind,
+    CurrentRowKind,
+    FollowingKind,
+    UnboundedFollowingKind,
+}
+
+/// `offset` is set for `n PRECEDING` and `n FOLLOWING`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct FrameBound{
+    pub offset: Option<Box<Expression>>,
+    pub kind:   FrameBoundKind
+}
+
+/// The rows of a partition an aggregate window function reads. A frame
+/// given only by its start ends at the current row.
+#[derive(PartialEq, Debug, Clone)]
+pub struct WindowFrame{
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end:   FrameBound
+}
+
+/// `table` qualifies an identifier literal, as in `users.id`. A parenthesized
+/// SELECT is a `subquery`, which `EXISTS` and `IN` take as their operand.
+#[derive(PartialEq, Debug, Clone)]
 pub struct Expression{
-    pub literal: Token,
-    pub kind: ExpressionKind
+    pub literal:  Option<Token>,
+    pub table:    Option<Token>,
+    pub binary:   Option<BinaryExpression>,
+    pub unary:    Option<UnaryExpression>,
+    pub function: Option<FunctionExpression>,
+    pub subquery: Option<Box<SelectStatement>>,
+    pub kind:     ExpressionKind
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct ColumnDefinition{
     pub name: Token,
-    pub datatype: Token
+    pub datatype: Token,
+    pub not_null: bool
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct CreateTableStatement{
     pub name: Token,
-    pub cols: Vec<ColumnDefinition>
+    pub cols: Vec<ColumnDefinition>,
+    pub if_not_exists: bool
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+pub enum AlterTableKind{
+    AddColumnKind,
+    DropColumnKind,
+    RenameColumnKind,
+    RenameTableKind,
+}
+
+/// `column_definition` is set for ADD COLUMN, `column` for DROP COLUMN and
+/// RENAME COLUMN, and `new_name` for both renames.
+#[derive(PartialEq, Debug, Clone)]
+pub struct AlterTableStatement{
+    pub table: Token,
+    pub column_definition: Option<ColumnDefinition>,
+    pub column: Option<Token>,
+    pub new_name: Option<Token>,
+    pub kind: AlterTableKind
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DropTableStatement{
+    pub name: Token,
+    pub if_exists: bool
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct SelectItem{
+    pub exp:      Option<Expression>,
+    pub asterisk: bool,
+    pub table:    Option<Token>,
+    pub as_name:  Option<Token>
+}
+
+/// A named query, as in `name (a, b) AS (SELECT ...)`, where the column
+/// list is optional.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CommonTableExpression{
+    pub name:    Token,
+    pub columns: Vec<Token>,
+    pub query:   SelectStatement
+}
+
+/// A SELECT along with the common table expressions of its WITH clause,
+/// which only it can read. With `recursive` set, a CTE can read itself.
+#[derive(PartialEq, Debug, Clone)]
+pub struct QueryStatement{
+    pub recursive: bool,
+    pub with:      Vec<CommonTableExpression>,
+    pub select:    SelectStatement
+}
+
+/// A compound query only sets `set_operation` and the `order_by`, `limit`
+/// and `offset` that apply to its result.
+#[derive(PartialEq, Debug, Clone)]
 pub struct SelectStatement{
-    pub item: Vec<Expression>,
-    pub from: Token
-}
\ No newline at end of file
+    pub distinct:      bool,
+    pub item:          Vec<SelectItem>,
+    pub from:          Option<FromItem>,
+    pub filter:        Option<Expression>,
+    pub group_by:      Vec<Expression>,
+    pub having:        Option<Expression>,
+    pub order_by:      Vec<OrderByItem>,
+    pub limit:         Option<Expression>,
+    pub offset:        Option<Expression>,
+    pub set_operation: Option<SetOperation>
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum SetOperator{
+    UnionOperator,
+    IntersectOperator,
+    ExceptOperator,
+}
+
+/// Combines the rows of two queries, keeping duplicates if `all` is set.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SetOperation{
+    pub left:  Box<SelectStatement>,
+    pub right: Box<SelectStatement>,
+    pub op:    SetOperator,
+    pub all:   bool
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum FromItemKind{
+    TableKind,
+    JoinKind,
+    SubqueryKind,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum JoinType{
+    InnerJoin,
+    LeftJoin,
+    CrossJoin,
+}
+
+/// `on` is unset for cross joins.
+#[derive(PartialEq, Debug, Clone)]
+pub struct JoinItem{
+    pub left:      Box<FromItem>,
+    pub right:     Box<FromItem>,
+    pub join_type: JoinType,
+    pub on:        Option<Expression>
+}
+
+/// A table, optionally aliased, a join of two FROM items, or a subquery,
+/// which must be aliased.
+#[derive(PartialEq, Debug, Clone)]
+pub struct FromItem{
+    pub table:    Option<Token>,
+    pub alias:    Option<Token>,
+    pub join:     Option<JoinItem>,
+    pub subquery: Option<Box<SelectStatement>>,
+    pub kind:     FromItemKind
+}
+
+/// `nulls_first` is resolved by the parser, defaulting to NULLS LAST for
+/// ascending and NULLS FIRST for descending order.
+#[derive(PartialEq, Debug, Clone)]
+pub struct OrderByItem{
+    pub exp:         Expression,
+    pub desc:        bool,
+    pub nulls_first: bool
+}
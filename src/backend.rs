@@ -1,19 +1,39 @@
+use std::fmt;
 use crate::ast::*;
+use crate::error::Error;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum ColumnType {
     TextType,
-    IntType
+    IntType,
+    BoolType,
+    /// Type of a bare NULL literal, compatible with every other type.
+    NullType
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColumnType::TextType => write!(f, "text"),
+            ColumnType::IntType => write!(f, "int"),
+            ColumnType::BoolType => write!(f, "boolean"),
+            ColumnType::NullType => write!(f, "unknown")
+        }
+    }
 }
 
 pub trait Cell {
-    fn as_text(&self) -> String;
-    fn as_int(&self) -> i32;
+    fn as_text(&self) -> Result<String, Error>;
+    fn as_int(&self) -> Result<i32, Error>;
+    fn as_bool(&self) -> Result<bool, Error>;
+    fn is_null(&self) -> bool;
 }
 
 pub struct Column {
     pub col_type: ColumnType,
     pub name: String,
+    /// Table name or alias the column was read from, if it is a plain column.
+    pub table: Option<String>,
 }
 
 pub struct Results {
@@ -21,15 +41,16 @@ pub struct Results {
     pub rows: Vec<Vec<Box<dyn Cell>>> 
 }
 
-
-pub const ERR_TABLE_DOES_NOT_EXIST: &str  = "Table does not exist";
-pub const ERR_COLUMN_DOES_NOT_EXIST: &str = "Column does not exist";
-//pub const ERR_INVALID_SELECT_ITEM: &str  = "Select item is not valid";
-//pub const ERR_INVALID_DATATYPE: &str    = "Invalid datatype";
-pub const ERR_MISSING_VALUES: &str      = "Missing values";
-
 pub trait Backend {
-    fn create_table(&mut self, create_table_statement: &CreateTableStatement) -> Result<bool, String>;
-    fn insert(&mut self, insert_statement: &InsertStatement) -> Result<bool, String>;
-    fn select(&self, select_statement: &SelectStatement) -> Result<Results, String>;
+    /// Returns false if the table already existed and IF NOT EXISTS was given.
+    fn create_table(&mut self, create_table_statement: &CreateTableStatement) -> Result<bool, Error>;
+    /// Returns false if the table did not exist and IF EXISTS was given.
+    fn drop_table(&mut self, drop_table_statement: &DropTableStatement) -> Result<bool, Error>;
+    fn alter_table(&mut self, alter_table_statement: &AlterTableStatement) -> Result<bool, Error>;
+    fn insert(&mut self, insert_statement: &InsertStatement) -> Result<bool, Error>;
+    fn select(&self, query_statement: &QueryStatement) -> Result<Results, Error>;
+    /// Returns the number of rows updated.
+    fn update(&mut self, update_statement: &UpdateStatement) -> Result<usize, Error>;
+    /// Returns the number of rows deleted.
+    fn delete(&mut self, delete_statement: &DeleteStatement) -> Result<usize, Error>;
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use std::fmt;
+use crate::lexer::*;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+    LexError{
+        msg: String,
+        loc: Location
+    },
+    ParseError{
+        msg: String,
+        loc: Location
+    },
+    TableDoesNotExist(String),
+    TableAlreadyExists(String),
+    ColumnDoesNotExist(String),
+    ColumnAlreadyExists(String),
+    AmbiguousColumn(String),
+    TypeMismatch(String),
+    UnsupportedType(String),
+    InvalidValue(String),
+    ArityMismatch{
+        expected: usize,
+        got: usize
+    },
+    InvalidExpression(String),
+    NotNullViolation(String),
+    DivisionByZero,
+    IntegerOverflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::LexError{msg, loc} => write!(f, "{}, at {}:{}", msg, loc.line, loc.col),
+            Error::ParseError{msg, loc} => write!(f, "[{},{}]: {}", loc.line, loc.col, msg),
+            Error::TableDoesNotExist(name) => write!(f, "Table \"{}\" does not exist", name),
+            Error::TableAlreadyExists(name) => write!(f, "Table \"{}\" already exists", name),
+            Error::ColumnDoesNotExist(name) => write!(f, "Column \"{}\" does not exist", name),
+            Error::ColumnAlreadyExists(name) => write!(f, "Column \"{}\" already exists", name),
+            Error::AmbiguousColumn(name) => write!(f, "Column reference \"{}\" is ambiguous", name),
+            Error::TypeMismatch(msg) => write!(f, "Type mismatch: {}", msg),
+            Error::UnsupportedType(name) => write!(f, "Unsupported type: {}", name),
+            Error::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
+            Error::ArityMismatch{expected, got} => write!(f, "Expected {} values, got {}", expected, got),
+            Error::InvalidExpression(msg) => write!(f, "Invalid expression: {}", msg),
+            Error::NotNullViolation(column) => write!(f, "Null value in column \"{}\" violates not-null constraint", column),
+            Error::DivisionByZero => write!(f, "Division by zero"),
+            Error::IntegerOverflow => write!(f, "Integer overflow"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
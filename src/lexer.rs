@@ -1,3 +1,5 @@
+use crate::error::Error;
+
 pub type Keyword = &'static str;
 
 pub const SELECT_KEYWORD: Keyword = "select";
@@ -16,6 +18,42 @@ pub const AND_KEYWORD: Keyword = "and";
 pub const OR_KEYWORD: Keyword = "or";
 pub const TRUE_KEYWORD: Keyword = "true";
 pub const FALSE_KEYWORD: Keyword = "false";
+pub const NOT_KEYWORD: Keyword = "not";
+pub const NULL_KEYWORD: Keyword = "null";
+pub const IS_KEYWORD: Keyword = "is";
+pub const UPDATE_KEYWORD: Keyword = "update";
+pub const SET_KEYWORD: Keyword = "set";
+pub const DELETE_KEYWORD: Keyword = "delete";
+pub const DROP_KEYWORD: Keyword = "drop";
+pub const IF_KEYWORD: Keyword = "if";
+pub const EXISTS_KEYWORD: Keyword = "exists";
+pub const ALTER_KEYWORD: Keyword = "alter";
+pub const ADD_KEYWORD: Keyword = "add";
+pub const COLUMN_KEYWORD: Keyword = "column";
+pub const RENAME_KEYWORD: Keyword = "rename";
+pub const TO_KEYWORD: Keyword = "to";
+pub const ORDER_KEYWORD: Keyword = "order";
+pub const BY_KEYWORD: Keyword = "by";
+pub const ASC_KEYWORD: Keyword = "asc";
+pub const DESC_KEYWORD: Keyword = "desc";
+pub const LIMIT_KEYWORD: Keyword = "limit";
+pub const OFFSET_KEYWORD: Keyword = "offset";
+pub const GROUP_KEYWORD: Keyword = "group";
+pub const HAVING_KEYWORD: Keyword = "having";
+pub const DISTINCT_KEYWORD: Keyword = "distinct";
+pub const JOIN_KEYWORD: Keyword = "join";
+pub const INNER_KEYWORD: Keyword = "inner";
+pub const LEFT_KEYWORD: Keyword = "left";
+pub const OUTER_KEYWORD: Keyword = "outer";
+pub const CROSS_KEYWORD: Keyword = "cross";
+pub const ON_KEYWORD: Keyword = "on";
+pub const IN_KEYWORD: Keyword = "in";
+pub const UNION_KEYWORD: Keyword = "union";
+pub const INTERSECT_KEYWORD: Keyword = "intersect";
+pub const EXCEPT_KEYWORD: Keyword = "except";
+pub const ALL_KEYWORD: Keyword = "all";
+pub const WITH_KEYWORD: Keyword = "with";
+pub const RECURSIVE_KEYWORD: Keyword = "recursive";
 
 pub type Symbol = &'static str;
 
@@ -28,8 +66,15 @@ pub const EQ_SYMBOL: Symbol = "=";
 pub const NEQ_SYMBOL: Symbol = "<>";
 pub const CONCAT_SYMBOL: Symbol = "||";
 pub const PLUS_SYMBOL: Symbol = "+";
-
-#[derive(Clone, Debug)]
+pub const MINUS_SYMBOL: Symbol = "-";
+pub const SLASH_SYMBOL: Symbol = "/";
+pub const LT_SYMBOL: Symbol = "<";
+pub const LTE_SYMBOL: Symbol = "<=";
+pub const GT_SYMBOL: Symbol = ">";
+pub const GTE_SYMBOL: Symbol = ">=";
+pub const DOT_SYMBOL: Symbol = ".";
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Location {
     pub line: usize,
     pub col: usize,
@@ -43,6 +88,7 @@ pub enum TokenKind {
     StringKind,
     NumericKind,
     BooleanKind,
+    NullKind,
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +112,12 @@ impl PartialEq for Token {
     }
 }
 
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Cursor {
     pub fn new() -> Cursor {
         Cursor {
@@ -75,12 +127,24 @@ impl Cursor {
     }
 }
 
+impl Default for Location {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Location {
     pub fn new() -> Location {
         Location { line: 0, col: 0 }
     }
 }
 
+impl Default for Token {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Token {
     pub fn new() -> Token {
         Token{
@@ -91,13 +155,13 @@ impl Token {
     }
 }
 
-pub fn lex(source: &str) -> Result<Vec<Token>, String> {
+pub fn lex(source: &str) -> Result<Vec<Token>, Error> {
     let mut tokens: Vec<Token> = vec![];
     let mut cur = Cursor {
         pointer: 0,
         loc: Location { line: 0, col: 0 },
     };
-    'lex: while &cur.pointer < &source.len() {
+    'lex: while cur.pointer < source.len() {
         let lexers: Vec<Lexer> = vec![
             lex_keyword,
             lex_symbol,
@@ -107,24 +171,23 @@ pub fn lex(source: &str) -> Result<Vec<Token>, String> {
         ];
 
         for l in lexers {
-            if let (token, new_cursor, true) = l(&source, cur.clone()) {
+            if let (token, new_cursor, true) = l(source, cur.clone()) {
                 cur = new_cursor;
-                match token {
-                    Some(token) => tokens.push(token),
-                    None => (),
+                if let Some(token) = token {
+                    tokens.push(token);
                 }
 
                 continue 'lex;
             }
         }
         let mut hint = String::new();
-        if tokens.len() > 0 {
+        if !tokens.is_empty() {
             hint = " after ".to_string() + &tokens[tokens.len() - 1].value;
         }
-        return Err(format!(
-            "Unable to lex token{}, at {}:{}",
-            hint, cur.loc.line, cur.loc.col
-        ));
+        return Err(Error::LexError {
+            msg: format!("Unable to lex token{}", hint),
+            loc: cur.loc,
+        });
     }
     Ok(tokens)
 }
@@ -138,7 +201,7 @@ pub fn lex_numeric(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
         let c = source.chars().nth(cur.pointer).unwrap();
         cur.loc.col += 1;
 
-        let is_digit = c >= '0' && c <= '9';
+        let is_digit = c.is_ascii_digit();
         let is_period = c == '.';
         let is_exp_marker = c == 'e';
 
@@ -191,11 +254,12 @@ pub fn lex_numeric(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
     if cur.pointer == ic.pointer {
         return (None, ic, false);
     }
+    cur.loc.col = ic.loc.col + (cur.pointer - ic.pointer);
 
     (
         Some(Token {
             value: source[ic.pointer..cur.pointer].to_string(),
-            loc: cur.loc.clone(),
+            loc: ic.loc,
             kind: TokenKind::NumericKind,
         }),
         cur,
@@ -210,7 +274,7 @@ pub fn lex_character_delimited(
 ) -> (Option<Token>, Cursor, bool) {
     let mut cur = ic.clone();
 
-    if source[cur.pointer..].len() == 0 {
+    if source[cur.pointer..].is_empty() {
         return (None, ic, false);
     }
     if source.chars().nth(cur.pointer).unwrap() != delimiter {
@@ -233,7 +297,7 @@ pub fn lex_character_delimited(
                 cur.loc.col += 1;
                 return (
                     Some(Token {
-                        value: value.into(),
+                        value,
                         loc: ic.loc,
                         kind: TokenKind::StringKind,
                     }),
@@ -257,7 +321,7 @@ pub fn lex_string(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
     lex_character_delimited(source, ic, '\'')
 }
 
-pub fn longest_match(source: &str, ic: Cursor, options: &Vec<&str>) -> String {
+pub fn longest_match(source: &str, ic: Cursor, options: &[&str]) -> String {
     let mut value = String::new();
     let mut skip_list: Vec<usize> = vec![];
     let mut matched = String::new();
@@ -280,7 +344,7 @@ pub fn longest_match(source: &str, ic: Cursor, options: &Vec<&str>) -> String {
                 }
             }
 
-            if option.to_string() == value {
+            if value == *option {
                 skip_list.push(index);
                 if option.len() > matched.len() {
                     matched = option.to_string();
@@ -299,7 +363,7 @@ pub fn longest_match(source: &str, ic: Cursor, options: &Vec<&str>) -> String {
             break;
         }
     }
-    return matched;
+    matched
 }
 
 pub fn lex_symbol(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
@@ -313,11 +377,18 @@ pub fn lex_symbol(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
         NEQ_SYMBOL,
         CONCAT_SYMBOL,
         PLUS_SYMBOL,
+        MINUS_SYMBOL,
+        SLASH_SYMBOL,
+        LT_SYMBOL,
+        LTE_SYMBOL,
+        GT_SYMBOL,
+        GTE_SYMBOL,
         COMMA_SYMBOL,
         LEFTPAREN_SYMBOL,
         RIGHTPAREN_SYMBOL,
         SEMICOLON_SYMBOL,
         ASTERISK_SYMBOL,
+        DOT_SYMBOL,
     ];
 
     match c {
@@ -328,6 +399,8 @@ pub fn lex_symbol(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
         }
         '\t' => (None, cur, true),
         ' ' => (None, cur, true),
+        // Leave numerics like `.5` to lex_numeric.
+        '.' if matches!(source.chars().nth(cur.pointer), Some(n) if n.is_ascii_digit()) => (None, ic, false),
         _ => match &longest_match(source, ic.clone(), &symbols)[..] {
             "" => (None, ic, false),
             matched => {
@@ -354,7 +427,7 @@ pub fn lex_identifier(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool)
     let mut cur = ic.clone();
     let mut c = source.chars().nth(cur.pointer).unwrap();
 
-    let mut is_alphabetical = (c >= 'A' && c <= 'Z') || (c >= 'a' && c <= 'z');
+    let mut is_alphabetical = c.is_ascii_alphabetic();
     if !is_alphabetical {
         return (None, ic, false);
     }
@@ -366,8 +439,8 @@ pub fn lex_identifier(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool)
     while cur.pointer < source.len() {
         c = source.chars().nth(cur.pointer).unwrap();
 
-        is_alphabetical = (c >= 'A' && c <= 'Z') || (c >= 'a' && c <= 'z');
-        let is_numeric = c >= '0' && c <= '9';
+        is_alphabetical = c.is_ascii_alphabetic();
+        let is_numeric = c.is_ascii_digit();
         if is_alphabetical || is_numeric || c == '$' || c == '_' {
             value.push(c);
             cur.loc.col += 1;
@@ -376,7 +449,7 @@ pub fn lex_identifier(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool)
         }
         break;
     }
-    if value.len() == 0 {
+    if value.is_empty() {
         return (None, ic, false);
     }
     (
@@ -409,16 +482,59 @@ pub fn lex_keyword(source: &str, ic: Cursor) -> (Option<Token>, Cursor, bool) {
         OR_KEYWORD,
         TRUE_KEYWORD,
         FALSE_KEYWORD,
+        NOT_KEYWORD,
+        NULL_KEYWORD,
+        IS_KEYWORD,
+        UPDATE_KEYWORD,
+        SET_KEYWORD,
+        DELETE_KEYWORD,
+        DROP_KEYWORD,
+        IF_KEYWORD,
+        EXISTS_KEYWORD,
+        ALTER_KEYWORD,
+        ADD_KEYWORD,
+        COLUMN_KEYWORD,
+        RENAME_KEYWORD,
+        TO_KEYWORD,
+        ORDER_KEYWORD,
+        BY_KEYWORD,
+        ASC_KEYWORD,
+        DESC_KEYWORD,
+        LIMIT_KEYWORD,
+        OFFSET_KEYWORD,
+        GROUP_KEYWORD,
+        HAVING_KEYWORD,
+        DISTINCT_KEYWORD,
+        JOIN_KEYWORD,
+        INNER_KEYWORD,
+        LEFT_KEYWORD,
+        OUTER_KEYWORD,
+        CROSS_KEYWORD,
+        ON_KEYWORD,
+        IN_KEYWORD,
+        UNION_KEYWORD,
+        INTERSECT_KEYWORD,
+        EXCEPT_KEYWORD,
+        ALL_KEYWORD,
+        WITH_KEYWORD,
+        RECURSIVE_KEYWORD,
     ];
 
     let matched = longest_match(source, ic.clone(), &options);
-    let kind: TokenKind;
-
-    match &matched[..] {
+    let kind = match &matched[..] {
         "" => return (None, ic, false),
-        TRUE_KEYWORD | FALSE_KEYWORD => kind = TokenKind::BooleanKind,
-        _ => kind = TokenKind::KeywordKind,
+        TRUE_KEYWORD | FALSE_KEYWORD => TokenKind::BooleanKind,
+        NULL_KEYWORD => TokenKind::NullKind,
+        _ => TokenKind::KeywordKind,
+    };
+
+    // A keyword must not be a prefix of a longer identifier, e.g. `or` in `orders`.
+    if let Some(c) = source.chars().nth(ic.pointer + matched.len()) {
+        if c.is_ascii_alphanumeric() || c == '$' || c == '_' {
+            return (None, ic, false);
+        }
     }
+
     cur.pointer = ic.pointer + matched.len();
     cur.loc.col = ic.loc.col + matched.len();
 
@@ -2,4 +2,5 @@ pub mod lexer;
 pub mod ast;
 pub mod parser;
 pub mod backend;
-pub mod memory;
\ No newline at end of file
+pub mod memory;
+pub mod error;
\ No newline at end of file
@@ -1,62 +1,100 @@
 use std::io;
 use std::io::Write;
-mod memory;
-mod backend;
-mod lexer;
-mod ast;
-mod parser;
+use rustsql::{memory, parser};
+use rustsql::ast::*;
+use rustsql::backend::*;
+use rustsql::error::Error;
 
-use ast::*;
-use backend::*;
+fn print_results(results: &Results) -> Result<(), Error> {
+    for col in &results.columns {
+        print!("| {} ", col.name);
+    }
+    println!("|");
+    println!("{}", "=".repeat(20));
+
+    for result in &results.rows {
+        print!("|");
+        for (i, cell) in result.iter().enumerate() {
+            let col_type = &results.columns[i].col_type;
+            let s = if cell.is_null() {
+                "NULL".to_string()
+            } else {
+                match col_type {
+                    ColumnType::IntType => cell.as_int()?.to_string(),
+                    ColumnType::TextType => cell.as_text()?,
+                    ColumnType::BoolType => cell.as_bool()?.to_string(),
+                    ColumnType::NullType => "NULL".to_string()
+                }
+            };
+            print!(" {} | ", s)
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn execute(backend: &mut dyn Backend, stmt: Statement) -> Result<(), Error> {
+    match stmt.kind {
+        AstKind::CreateTableKind => {
+            backend.create_table(&stmt.create_table_statement.unwrap())?;
+        },
+        AstKind::AlterTableKind => {
+            backend.alter_table(&stmt.alter_table_statement.unwrap())?;
+        },
+        AstKind::DropTableKind => {
+            backend.drop_table(&stmt.drop_table_statement.unwrap())?;
+        },
+        AstKind::InsertKind => {
+            backend.insert(&stmt.insert_statement.unwrap())?;
+        },
+        AstKind::UpdateKind => {
+            let count = backend.update(&stmt.update_statement.unwrap())?;
+            println!("UPDATE {}", count);
+        },
+        AstKind::DeleteKind => {
+            let count = backend.delete(&stmt.delete_statement.unwrap())?;
+            println!("DELETE {}", count);
+        },
+        AstKind::SelectKind => {
+            let results = backend.select(&stmt.select_statement.unwrap())?;
+            print_results(&results)?;
+        }
+    }
+    Ok(())
+}
 
 fn main() {
     let mut memory_backend = memory::MemoryBackend::new();
     println!("Welcome to gosql.");
     loop {
         print!("# ");
-        io::stdout().flush();
+        io::stdout().flush().unwrap();
         let mut line = String::new();
-        io::stdin().read_line(&mut line).unwrap();
-        let line = line.trim_end();
-        let ast = parser::parse(&line);
-        if ast.is_err() {
-            eprintln!("{:?}", ast);
-            continue;
+        match io::stdin().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => (),
+            Err(err) => {
+                eprintln!("{}", err);
+                break;
+            }
         }
+        let line = line.trim_end();
+        let ast = match parser::parse(line) {
+            Ok(ast) => ast,
+            Err(err) => {
+                eprintln!("{}", err);
+                continue;
+            }
+        };
 
-        for stmt in ast.unwrap().statements {
-            match stmt.kind {
-                AstKind::CreateTableKind => {
-                    memory_backend.create_table(&stmt.create_table_statement.unwrap()).unwrap();
-                    println!("ok");
-                },
-                AstKind::InsertKind => {
-                    memory_backend.insert(&stmt.insert_statement.unwrap()).unwrap();
-                    println!("ok");
-                },
-                AstKind::SelectKind => {
-                    let results = memory_backend.select(&stmt.select_statement.unwrap()).unwrap();
-                    for col in &results.columns {
-                        print!("| {} ", col.name);
-                    }
-                    println!("|");
-                    println!("{}", "=".repeat(20));
-
-                    for result in results.rows {
-                        print!("|");
-                        for (i, cell) in result.iter().enumerate() {
-                            let col_type = &results.columns[i].col_type;
-                            let s = match col_type {
-                                ColumnType::IntType => cell.as_int().to_string(),
-                                ColumnType::TextType => cell.as_text()
-                            };
-                            print!(" {} | ", s)
-                        }
-                        println!("");
-                    }
-                    println!("ok");
+        for stmt in ast.statements {
+            match execute(&mut memory_backend, stmt) {
+                Ok(()) => println!("ok"),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    break;
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -1,158 +1,2183 @@
-use core::ops::Deref;
 use crate::backend::*;
 use crate::lexer::*;
 use crate::ast::*;
-use std::collections::BTreeMap;
+use crate::error::Error;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::cmp::Ordering;
+use std::borrow::Cow;
+use std::cell::OnceCell;
 
 use std::convert::TryInto;
 
-#[derive(Clone, Debug)]
-struct MemoryCell(Vec<u8>);
+/// Rows read by a query, borrowed from a stored table where possible.
+type Rows<'a> = Cow<'a, [Vec<MemoryCell>]>;
 
-impl Deref for MemoryCell {
-    type Target = Vec<u8>;
-    fn deref(&self) -> &Self::Target {
-        &self.0
+/// Materialized common table expressions visible to a query, by name.
+type Ctes = BTreeMap<String, Table>;
+
+/// A stored value, `None` for NULL.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct MemoryCell(Option<Vec<u8>>);
+
+impl Cell for MemoryCell {
+    fn as_int(&self) -> Result<i32, Error> {
+        match self.bytes()?.try_into() {
+            Ok(bytes) => Ok(i32::from_be_bytes(bytes)),
+            Err(_) => Err(Error::InvalidValue(format!("{:?} is not an int", self.0)))
+        }
+    }
+
+    fn as_text(&self) -> Result<String, Error> {
+        String::from_utf8(self.bytes()?.to_vec())
+            .map_err(|_| Error::InvalidValue(format!("{:?} is not valid text", self.0)))
+    }
+
+    fn as_bool(&self) -> Result<bool, Error> {
+        match self.bytes()? {
+            [b] => Ok(*b != 0),
+            _ => Err(Error::InvalidValue(format!("{:?} is not a boolean", self.0)))
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        self.0.is_none()
     }
 }
 
-impl Cell for MemoryCell {
-    fn as_int(&self) -> i32 {
-        i32::from_be_bytes(self[..].try_into().unwrap())
+impl MemoryCell {
+    fn null() -> MemoryCell {
+        MemoryCell(None)
+    }
+
+    fn from_int(i: i32) -> MemoryCell {
+        MemoryCell(Some(i.to_be_bytes().to_vec()))
+    }
+
+    fn from_text(s: &str) -> MemoryCell {
+        MemoryCell(Some(s.as_bytes().to_vec()))
     }
 
-    fn as_text(&self) -> String {
-        String::from_utf8(self.to_vec()).unwrap()
+    fn from_bool(b: bool) -> MemoryCell {
+        MemoryCell(Some(vec!{b as u8}))
+    }
+
+    fn bytes(&self) -> Result<&[u8], Error> {
+        match &self.0 {
+            Some(bytes) => Ok(bytes),
+            None => Err(Error::InvalidValue("unexpected NULL".to_string()))
+        }
+    }
+
+    fn to_text(&self, col_type: &ColumnType) -> Result<String, Error> {
+        if self.is_null() {
+            return Ok("NULL".to_string());
+        }
+
+        match col_type {
+            ColumnType::IntType => Ok(self.as_int()?.to_string()),
+            ColumnType::TextType => self.as_text(),
+            ColumnType::BoolType => Ok(self.as_bool()?.to_string()),
+            ColumnType::NullType => Ok("NULL".to_string())
+        }
+    }
+
+    /// Orders two cells of the same type, sorting NULL after every value.
+    fn compare(&self, other: &MemoryCell, col_type: &ColumnType) -> Result<Ordering, Error> {
+        match (self.is_null(), other.is_null()) {
+            (true, true) => return Ok(Ordering::Equal),
+            (true, false) => return Ok(Ordering::Greater),
+            (false, true) => return Ok(Ordering::Less),
+            _ => ()
+        }
+
+        match col_type {
+            ColumnType::IntType => Ok(self.as_int()?.cmp(&other.as_int()?)),
+            ColumnType::TextType => Ok(self.as_text()?.cmp(&other.as_text()?)),
+            ColumnType::BoolType => Ok(self.as_bool()?.cmp(&other.as_bool()?)),
+            ColumnType::NullType => Ok(Ordering::Equal)
+        }
     }
 }
 
-struct Table {
-    columns: Vec<String>,
-    column_types: Vec<ColumnType>,
-    rows: Vec<Vec<MemoryCell>>
+fn count_cell(count: usize) -> Result<MemoryCell, Error> {
+    match count.try_into() {
+        Ok(i) => Ok(MemoryCell::from_int(i)),
+        Err(_) => Err(Error::IntegerOverflow)
+    }
 }
 
-pub struct MemoryBackend {
-    tables: BTreeMap<String, Table>
+/// The expressions a function call reads from each row it is evaluated on:
+/// its arguments and, for a window function, its PARTITION BY and ORDER BY.
+fn function_operands(function: &FunctionExpression) -> Vec<&Expression> {
+    let mut operands: Vec<&Expression> = function.args.iter().collect();
+    if let Some(over) = &function.over {
+        operands.extend(&over.partition_by);
+        operands.extend(over.order_by.iter().map(|item| &item.exp));
+    }
+    operands
 }
 
-impl MemoryBackend{
-    pub fn new() -> MemoryBackend {
-        MemoryBackend{
-            tables: BTreeMap::new()
+/// Collects the distinct aggregate calls in `exp`. A window function call
+/// is not one, though its operands may contain some.
+fn collect_aggregates(exp: &Expression, aggregates: &mut Vec<Expression>) {
+    match exp.kind {
+        ExpressionKind::LiteralKind => (),
+        ExpressionKind::UnaryKind => collect_aggregates(&exp.unary.as_ref().unwrap().operand, aggregates),
+        ExpressionKind::BinaryKind => {
+            let binary = exp.binary.as_ref().unwrap();
+            collect_aggregates(&binary.a, aggregates);
+            collect_aggregates(&binary.b, aggregates);
+        },
+        ExpressionKind::FunctionKind => {
+            let function = exp.function.as_ref().unwrap();
+            if function.over.is_some() {
+                for operand in function_operands(function) {
+                    collect_aggregates(operand, aggregates);
+                }
+            } else if !aggregates.contains(exp) {
+                aggregates.push(exp.clone());
+            }
+        },
+        ExpressionKind::SubqueryKind => ()
+    }
+}
+
+/// Collects the distinct window function calls in `exp`.
+fn collect_windows(exp: &Expression, windows: &mut Vec<Expression>) {
+    match exp.kind {
+        ExpressionKind::LiteralKind | ExpressionKind::SubqueryKind => (),
+        ExpressionKind::UnaryKind => collect_windows(&exp.unary.as_ref().unwrap().operand, windows),
+        ExpressionKind::BinaryKind => {
+            let binary = exp.binary.as_ref().unwrap();
+            collect_windows(&binary.a, windows);
+            collect_windows(&binary.b, windows);
+        },
+        ExpressionKind::FunctionKind => {
+            let function = exp.function.as_ref().unwrap();
+            if function.over.is_none() {
+                for arg in &function.args {
+                    collect_windows(arg, windows);
+                }
+            } else if !windows.contains(exp) {
+                windows.push(exp.clone());
+            }
         }
     }
+}
 
-    fn token_to_cell(token: &Token) -> MemoryCell {
-        match token.kind {
-            TokenKind::NumericKind => MemoryCell(token.value.parse::<i32>().unwrap().to_be_bytes().to_vec()),
-            TokenKind::StringKind => MemoryCell(token.value.as_bytes().to_vec()),
-            _ => panic!("unknown kind")
+/// Whether `exp` contains a window function call, not counting those in
+/// subqueries.
+fn contains_window(exp: &Expression) -> bool {
+    let mut windows: Vec<Expression> = vec!{};
+    collect_windows(exp, &mut windows);
+    !windows.is_empty()
+}
+
+/// Collects the distinct scalar subqueries, EXISTS and IN expressions in
+/// `exp`, each after any it depends on. Aggregate arguments are not
+/// searched, since they are evaluated on different rows.
+fn collect_subqueries(exp: &Expression, subqueries: &mut Vec<Expression>) {
+    match exp.kind {
+        ExpressionKind::LiteralKind => return,
+        ExpressionKind::FunctionKind => {
+            // Window function operands are read from the same rows as the
+            // call itself.
+            let function = exp.function.as_ref().unwrap();
+            if function.over.is_some() {
+                for operand in function_operands(function) {
+                    collect_subqueries(operand, subqueries);
+                }
+            }
+            return;
+        },
+        ExpressionKind::SubqueryKind => (),
+        ExpressionKind::UnaryKind => {
+            let unary = exp.unary.as_ref().unwrap();
+            if unary.op.value != EXISTS_KEYWORD {
+                collect_subqueries(&unary.operand, subqueries);
+                return;
+            }
+        },
+        ExpressionKind::BinaryKind => {
+            let binary = exp.binary.as_ref().unwrap();
+            collect_subqueries(&binary.a, subqueries);
+            if binary.op.value != IN_KEYWORD {
+                collect_subqueries(&binary.b, subqueries);
+                return;
+            }
         }
-    
+    }
+
+    if !subqueries.contains(exp) {
+        subqueries.push(exp.clone());
     }
 }
 
-impl Backend for MemoryBackend {
-    fn create_table(&mut self, crt: &CreateTableStatement) -> Result<bool, String> {
-        let mut table = Table{
+/// The query of a scalar subquery, EXISTS or IN expression.
+fn subquery_statement(exp: &Expression) -> &SelectStatement {
+    match exp.kind {
+        ExpressionKind::UnaryKind => exp.unary.as_ref().unwrap().operand.subquery.as_ref().unwrap(),
+        ExpressionKind::BinaryKind => exp.binary.as_ref().unwrap().b.subquery.as_ref().unwrap(),
+        _ => exp.subquery.as_ref().unwrap()
+    }
+}
+
+/// Checks that `exp` only reads columns through GROUP BY expressions or
+/// aggregate calls. Columns are compared by what they resolve to, so `id`
+/// and `users.id` are the same.
+fn check_grouped(table: &Table, exp: &Expression, group_by: &[Expression]) -> Result<(), Error> {
+    if group_by.contains(exp) {
+        return Ok(());
+    }
+
+    match exp.kind {
+        ExpressionKind::LiteralKind => match &exp.literal {
+            Some(lit) if lit.kind == TokenKind::IdentifierKind => {
+                // Columns of an enclosing query are constant within a group.
+                let j = table.resolve_column(&exp.table, lit)?;
+                if table.depth(j) > 0 {
+                    return Ok(());
+                }
+                check_grouped_column(table, j, group_by)
+            },
+            _ => Ok(())
+        },
+        ExpressionKind::UnaryKind => check_grouped(table, &exp.unary.as_ref().unwrap().operand, group_by),
+        ExpressionKind::BinaryKind => {
+            let binary = exp.binary.as_ref().unwrap();
+            check_grouped(table, &binary.a, group_by)?;
+            check_grouped(table, &binary.b, group_by)
+        },
+        ExpressionKind::FunctionKind => {
+            let function = exp.function.as_ref().unwrap();
+            if function.over.is_none() {
+                return Ok(());
+            }
+            for operand in function_operands(function) {
+                check_grouped(table, operand, group_by)?;
+            }
+            Ok(())
+        },
+        ExpressionKind::SubqueryKind => Ok(())
+    }
+}
+
+/// Checks that column `j` of `table` is a GROUP BY expression.
+fn check_grouped_column(table: &Table, j: usize, group_by: &[Expression]) -> Result<(), Error> {
+    let grouped = group_by.iter().any(|g| match &g.literal {
+        Some(lit) if lit.kind == TokenKind::IdentifierKind => table.resolve_column(&g.table, lit).ok() == Some(j),
+        _ => false
+    });
+    if grouped {
+        return Ok(());
+    }
+    Err(ungrouped_column(&table.columns[j]))
+}
+
+/// Whether a value of type `t` can be used where `expected` is required.
+fn is_type(t: &ColumnType, expected: &ColumnType) -> bool {
+    t == expected || *t == ColumnType::NullType
+}
+
+fn unsupported_operator(op: &Token) -> Error {
+    Error::InvalidExpression(format!("unsupported operator {}", op.value))
+}
+
+fn unary_type_mismatch(op: &Token, operand: &ColumnType) -> Error {
+    Error::TypeMismatch(format!("{} {}", op.value, operand))
+}
+
+fn binary_type_mismatch(op: &Token, a: &ColumnType, b: &ColumnType) -> Error {
+    Error::TypeMismatch(format!("{} {} {}", a, op.value, b))
+}
+
+fn ungrouped_column(name: &str) -> Error {
+    Error::InvalidExpression(format!("column \"{}\" must appear in the GROUP BY clause or be used in an aggregate function", name))
+}
+
+fn function_not_allowed(function: &FunctionExpression) -> Error {
+    let kind = if function.over.is_some() { "window" } else { "aggregate" };
+    Error::InvalidExpression(format!("{} function {} is not allowed here", kind, function.name.value))
+}
+
+/// The empty table a common table expression's result is stored in, with
+/// columns named by its column list if it has one.
+fn cte_table(cte: &CommonTableExpression, columns: &[Column]) -> Result<Table, Error> {
+    if !cte.columns.is_empty() && cte.columns.len() != columns.len() {
+        return Err(Error::InvalidExpression(format!(
+            "WITH query \"{}\" has {} columns available but {} columns specified",
+            cte.name.value,
+            columns.len(),
+            cte.columns.len()
+        )));
+    }
+
+    let mut names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+    for (j, column) in cte.columns.iter().enumerate() {
+        names[j] = column.value.clone();
+    }
+    Ok(Table{
+        columns: names,
+        column_types: columns.iter().map(|c| c.col_type.clone()).collect(),
+        not_null: vec!{false; columns.len()},
+        ..Table::new()
+    })
+}
+
+/// Whether `slct` reads the table called `name` anywhere, including in
+/// subqueries.
+fn reads_table(slct: &SelectStatement, name: &str) -> bool {
+    if let Some(set) = &slct.set_operation {
+        return reads_table(&set.left, name) || reads_table(&set.right, name);
+    }
+
+    let from_reads = match &slct.from {
+        Some(from) => from_reads_table(from, name),
+        None => false
+    };
+    from_reads || slct.item.iter().filter_map(|item| item.exp.as_ref())
+        .chain(&slct.filter)
+        .chain(&slct.group_by)
+        .chain(&slct.having)
+        .chain(slct.order_by.iter().map(|item| &item.exp))
+        .any(|exp| expression_reads_table(exp, name))
+}
+
+fn from_reads_table(from: &FromItem, name: &str) -> bool {
+    match from.kind {
+        FromItemKind::TableKind => from.table.as_ref().unwrap().value == name,
+        FromItemKind::SubqueryKind => reads_table(from.subquery.as_ref().unwrap(), name),
+        FromItemKind::JoinKind => {
+            let join = from.join.as_ref().unwrap();
+            from_reads_table(&join.left, name)
+                || from_reads_table(&join.right, name)
+                || join.on.as_ref().is_some_and(|on| expression_reads_table(on, name))
+        }
+    }
+}
+
+fn expression_reads_table(exp: &Expression, name: &str) -> bool {
+    match exp.kind {
+        ExpressionKind::LiteralKind => false,
+        ExpressionKind::UnaryKind => expression_reads_table(&exp.unary.as_ref().unwrap().operand, name),
+        ExpressionKind::BinaryKind => {
+            let binary = exp.binary.as_ref().unwrap();
+            expression_reads_table(&binary.a, name) || expression_reads_table(&binary.b, name)
+        },
+        ExpressionKind::FunctionKind => function_operands(exp.function.as_ref().unwrap()).into_iter().any(|operand| expression_reads_table(operand, name)),
+        ExpressionKind::SubqueryKind => reads_table(exp.subquery.as_ref().unwrap(), name)
+    }
+}
+
+/// Resolves `ORDER BY n`, which sorts on the n-th of `columns`, counting
+/// from one.
+fn order_by_position(exp: &Expression, columns: &[Column]) -> Result<Option<usize>, Error> {
+    match &exp.literal {
+        Some(lit) if lit.kind == TokenKind::NumericKind => match lit.value.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= columns.len() => Ok(Some(n - 1)),
+            _ => Err(Error::InvalidExpression(format!("ORDER BY position {} is not in select list", lit.value)))
+        },
+        _ => Ok(None)
+    }
+}
+
+/// Orders two rows' ORDER BY keys, which have the types given in `order_by`.
+fn compare_keys(a: &[MemoryCell], b: &[MemoryCell], items: &[OrderByItem], order_by: &[(Option<usize>, ColumnType)]) -> Result<Ordering, Error> {
+    for (i, item) in items.iter().enumerate() {
+        let ordering = match (a[i].is_null(), b[i].is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) if item.nulls_first => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, true) if item.nulls_first => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            _ => match a[i].compare(&b[i], &order_by[i].1)? {
+                ordering if item.desc => ordering.reverse(),
+                ordering => ordering
+            }
+        };
+        if ordering != Ordering::Equal {
+            return Ok(ordering);
+        }
+    }
+    Ok(Ordering::Equal)
+}
+
+/// Sorts `(keys, value)` rows by their ORDER BY keys, which have the
+/// types given in `order_by`. The sort is stable.
+fn sort_rows<T>(rows: &mut [(Vec<MemoryCell>, T)], items: &[OrderByItem], order_by: &[(Option<usize>, ColumnType)]) -> Result<(), Error> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let mut err = None;
+    rows.sort_by(|(a, _), (b, _)| match compare_keys(a, b, items, order_by) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            err.get_or_insert(e);
+            Ordering::Equal
+        }
+    });
+
+    match err {
+        Some(e) => Err(e),
+        None => Ok(())
+    }
+}
+
+/// Evaluates a constant LIMIT or OFFSET count, `None` if absent or NULL.
+fn evaluate_count(clause: &str, exp: Option<&Expression>) -> Result<Option<usize>, Error> {
+    let exp = match exp {
+        Some(exp) => exp,
+        None => return Ok(None)
+    };
+
+    let (cell, col_type) = Table::new().evaluate_cell(&[], exp)?;
+    if !is_type(&col_type, &ColumnType::IntType) {
+        return Err(Error::TypeMismatch(format!("{} must be int, got {}", clause.to_uppercase(), col_type)));
+    }
+    if cell.is_null() {
+        return Ok(None);
+    }
+
+    match cell.as_int()?.try_into() {
+        Ok(count) => Ok(Some(count)),
+        Err(_) => Err(Error::InvalidValue(format!("{} must not be negative", clause.to_uppercase())))
+    }
+}
+
+/// Evaluates the constant offset of an `n PRECEDING` or `n FOLLOWING`
+/// frame bound.
+fn frame_offset(bound: &FrameBound) -> Result<usize, Error> {
+    match evaluate_count("frame offset", bound.offset.as_deref())? {
+        Some(offset) => Ok(offset),
+        None => Err(Error::InvalidValue("FRAME OFFSET must not be NULL".to_string()))
+    }
+}
+
+/// Position in a partition of `n` sorted rows where a frame starts, or with
+/// `end` set, where it stops, for the row at `p` whose peers are at
+/// `peers`. Bounds are clamped to the partition.
+fn frame_position(frame: &WindowFrame, end: bool, p: usize, peers: (usize, usize), n: usize) -> Result<usize, Error> {
+    let bound = if end { &frame.end } else { &frame.start };
+    let rows = frame.units == FrameUnits::RowsUnits;
+    let position = match bound.kind {
+        FrameBoundKind::UnboundedPrecedingKind => 0,
+        FrameBoundKind::UnboundedFollowingKind => n,
+        FrameBoundKind::CurrentRowKind => match (rows, end) {
+            (true, false) => p,
+            (true, true) => p + 1,
+            (false, false) => peers.0,
+            (false, true) => peers.1
+        },
+        FrameBoundKind::PrecedingKind => (p + end as usize).saturating_sub(frame_offset(bound)?),
+        FrameBoundKind::FollowingKind => (p + end as usize).saturating_add(frame_offset(bound)?)
+    };
+    Ok(position.min(n))
+}
+
+/// A column of a query's result: a source column expanded from `*`, read
+/// by position, or an expression evaluated on each row.
+enum Output<'a> {
+    ColumnOutput(usize),
+    ExpressionOutput(&'a Expression)
+}
+
+#[derive(Clone)]
+struct Table {
+    columns: Vec<String>,
+    column_types: Vec<ColumnType>,
+    not_null: Vec<bool>,
+    /// Table name or alias each column is qualified by while a query runs;
+    /// empty for stored tables.
+    qualifiers: Vec<String>,
+    /// How many queries out each column comes from, 0 for the query's own
+    /// columns; missing entries are 0.
+    depths: Vec<usize>,
+    /// Whether each column belongs to an enclosing grouped query without
+    /// being grouped on, so that subqueries cannot read it; missing entries
+    /// are false.
+    ungrouped: Vec<bool>,
+    rows: Vec<Vec<MemoryCell>>,
+    /// Aggregate calls whose values follow the regular columns in each row
+    /// of a grouped table.
+    aggregates: Vec<Expression>,
+    /// Subquery expressions and their types, whose values follow the
+    /// aggregates in each row.
+    subqueries: Vec<(Expression, ColumnType)>,
+    /// For each subquery that reads no columns of the row, its rows once
+    /// first computed; `None` for correlated subqueries.
+    subquery_rows: Vec<Option<OnceCell<Vec<Vec<MemoryCell>>>>>,
+    /// Window function calls and their types, whose values follow the
+    /// subqueries in each row.
+    windows: Vec<(Expression, ColumnType)>
+}
+
+impl Table {
+    fn new() -> Table {
+        Table{
             columns: vec!{},
             column_types: vec!{},
+            not_null: vec!{},
+            qualifiers: vec!{},
+            depths: vec!{},
+            ungrouped: vec!{},
             rows: vec!{},
+            aggregates: vec!{},
+            subqueries: vec!{},
+            subquery_rows: vec!{},
+            windows: vec!{},
+        }
+    }
+
+    fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|col| col == name)
+    }
+
+    fn depth(&self, j: usize) -> usize {
+        self.depths.get(j).copied().unwrap_or(0)
+    }
+
+    /// Resolves a possibly qualified column reference, which must match
+    /// exactly one column of the innermost query that has a match.
+    fn resolve_column(&self, table: &Option<Token>, name: &Token) -> Result<usize, Error> {
+        let max_depth = self.depths.iter().copied().max().unwrap_or(0);
+        for depth in 0..=max_depth {
+            let mut found = None;
+            for (j, col) in self.columns.iter().enumerate() {
+                if *col != name.value || self.depth(j) != depth {
+                    continue;
+                }
+                if let Some(t) = table {
+                    if self.qualifiers.get(j) != Some(&t.value) {
+                        continue;
+                    }
+                }
+                if found.is_some() {
+                    return Err(Error::AmbiguousColumn(name.value.clone()));
+                }
+                found = Some(j);
+            }
+
+            if let Some(j) = found {
+                if depth > 0 && self.ungrouped.get(j) == Some(&true) {
+                    return Err(ungrouped_column(&self.columns[j]));
+                }
+                return Ok(j);
+            }
+        }
+
+        match table {
+            Some(t) => Err(Error::ColumnDoesNotExist(format!("{}.{}", t.value, name.value))),
+            None => Err(Error::ColumnDoesNotExist(name.value.clone()))
+        }
+    }
+
+    /// Appends the columns of an enclosing query, which rows of this table
+    /// then carry after their own.
+    fn add_outer(&mut self, outer: &Table) {
+        self.depths.resize(self.columns.len(), 0);
+        self.ungrouped.resize(self.columns.len(), false);
+        for j in 0..outer.columns.len() {
+            self.columns.push(outer.columns[j].clone());
+            self.column_types.push(outer.column_types[j].clone());
+            self.not_null.push(false);
+            self.qualifiers.push(outer.qualifiers.get(j).cloned().unwrap_or_default());
+            self.depths.push(outer.depth(j) + 1);
+            self.ungrouped.push(outer.ungrouped.get(j).copied().unwrap_or(false));
+        }
+    }
+
+    /// Position of `exp` among the table's subqueries, if it is one.
+    fn subquery_index(&self, exp: &Expression) -> Option<usize> {
+        let is_subquery = match exp.kind {
+            ExpressionKind::SubqueryKind => true,
+            ExpressionKind::UnaryKind => exp.unary.as_ref().unwrap().op.value == EXISTS_KEYWORD,
+            ExpressionKind::BinaryKind => exp.binary.as_ref().unwrap().op.value == IN_KEYWORD,
+            _ => false
         };
+        if !is_subquery {
+            return None;
+        }
+        self.subqueries.iter().position(|(e, _)| e == exp)
+    }
 
-        for column in &crt.cols {
-            table.columns.push(column.name.value.clone());
+    /// Position of `exp` among the table's window function calls, if it is one.
+    fn window_index(&self, exp: &Expression) -> Option<usize> {
+        match &exp.function {
+            Some(function) if function.over.is_some() => self.windows.iter().position(|(e, _)| e == exp),
+            _ => None
+        }
+    }
+
+    /// A rowless copy of the table's columns qualified by `qualifier`.
+    fn schema(&self, qualifier: &str) -> Table {
+        Table{
+            columns: self.columns.clone(),
+            column_types: self.column_types.clone(),
+            not_null: self.not_null.clone(),
+            qualifiers: vec!{qualifier.to_string(); self.columns.len()},
+            ..Table::new()
+        }
+    }
+
+    /// Appends a column, filling it with NULL in every existing row.
+    fn add_column(&mut self, column: &ColumnDefinition) -> Result<(), Error> {
+        if self.column_index(&column.name.value).is_some() {
+            return Err(Error::ColumnAlreadyExists(column.name.value.clone()));
+        }
+        if column.not_null && !self.rows.is_empty() {
+            return Err(Error::NotNullViolation(column.name.value.clone()));
+        }
+
+        let datatype = match &column.datatype.value[..] {
+            INT_KEYWORD => ColumnType::IntType,
+            TEXT_KEYWORD => ColumnType::TextType,
+            BOOL_KEYWORD => ColumnType::BoolType,
+            _ => return Err(Error::UnsupportedType(column.datatype.value.clone()))
+        };
+
+        self.columns.push(column.name.value.clone());
+        self.column_types.push(datatype);
+        self.not_null.push(column.not_null);
+        for row in &mut self.rows {
+            row.push(MemoryCell::null());
+        }
+        Ok(())
+    }
+
+    fn drop_column(&mut self, name: &Token) -> Result<(), Error> {
+        let j = match self.column_index(&name.value) {
+            Some(j) => j,
+            None => return Err(Error::ColumnDoesNotExist(name.value.clone()))
+        };
+
+        self.columns.remove(j);
+        self.column_types.remove(j);
+        self.not_null.remove(j);
+        for row in &mut self.rows {
+            row.remove(j);
+        }
+        Ok(())
+    }
+
+    fn rename_column(&mut self, name: &Token, new_name: &Token) -> Result<(), Error> {
+        let j = match self.column_index(&name.value) {
+            Some(j) => j,
+            None => return Err(Error::ColumnDoesNotExist(name.value.clone()))
+        };
+        if self.column_index(&new_name.value).is_some() {
+            return Err(Error::ColumnAlreadyExists(new_name.value.clone()));
+        }
+
+        self.columns[j] = new_name.value.clone();
+        Ok(())
+    }
 
-            let datatype = match &column.datatype.value[..] {
-                "int" => ColumnType::IntType,
-                "text" => ColumnType::TextType,
-                _ => panic!("wrong type")
+    /// Resolves the columns named by an INSERT or UPDATE to their indexes.
+    fn target_columns(&self, columns: Vec<&Token>) -> Result<Vec<usize>, Error> {
+        let mut targets = vec!{};
+        for column in columns {
+            let j = match self.column_index(&column.value) {
+                Some(j) => j,
+                None => return Err(Error::ColumnDoesNotExist(column.value.clone()))
             };
+            if targets.contains(&j) {
+                return Err(Error::InvalidExpression(format!("column \"{}\" specified more than once", column.value)));
+            }
+            targets.push(j);
+        }
+        Ok(targets)
+    }
 
-            table.column_types.push(datatype);
+    /// Checks that a value of `cell_type` can be stored in column `j`.
+    fn check_type(&self, j: usize, cell: &MemoryCell, cell_type: &ColumnType) -> Result<(), Error> {
+        if !is_type(cell_type, &self.column_types[j]) {
+            return Err(Error::TypeMismatch(format!(
+                "column \"{}\" is {}, got {} value {}",
+                self.columns[j],
+                self.column_types[j],
+                cell_type,
+                cell.to_text(cell_type)?
+            )));
         }
+        Ok(())
+    }
 
-        self.tables.insert(crt.name.value.clone(), table);
-        
-        Ok(true)
+    fn check_not_null(&self, row: &[MemoryCell]) -> Result<(), Error> {
+        for (j, cell) in row.iter().enumerate() {
+            if self.not_null[j] && cell.is_null() {
+                return Err(Error::NotNullViolation(self.columns[j].clone()));
+            }
+        }
+        Ok(())
     }
 
-    fn insert(&mut self, inst: &InsertStatement) -> Result<bool, String> { 
-        match self.tables.get_mut(&inst.table.value) {
-            Some(table) => {
-                if inst.values.len() != table.columns.len() {
-                    return Err(ERR_MISSING_VALUES.to_string());
-                }
+    /// Name and type of the column a select item produces.
+    fn expression_column(&self, exp: &Expression) -> Result<Column, Error> {
+        let (name, table) = match (&exp.literal, &exp.function) {
+            (Some(lit), _) if lit.kind == TokenKind::IdentifierKind => {
+                let j = self.resolve_column(&exp.table, lit)?;
+                (lit.value.clone(), self.qualifiers.get(j).cloned())
+            },
+            (_, Some(function)) => (function.name.value.clone(), None),
+            _ => ("?column?".to_string(), None)
+        };
+
+        Ok(Column{
+            col_type: self.expression_type(exp)?,
+            name,
+            table
+        })
+    }
 
-                let mut row: Vec<MemoryCell> = vec!{};
+    fn function_type(&self, function: &FunctionExpression) -> Result<ColumnType, Error> {
+        let name = &function.name.value[..];
+        match name {
+            "count" | "sum" | "avg" | "min" | "max" => (),
+            "row_number" | "rank" | "dense_rank" | "lag" | "lead" => {
+                return Err(Error::InvalidExpression(format!("window function {} requires an OVER clause", name)));
+            },
+            _ => return Err(Error::InvalidExpression(format!("function {} does not exist", name)))
+        }
 
-                for value in &inst.values {
-                    if value.kind != ExpressionKind::LiteralKind {
-                        println!("Skipping non-literal.");
-                        continue;
-                    }
-                    row.push(MemoryBackend::token_to_cell(&value.literal))
+        if function.asterisk {
+            if name != "count" {
+                return Err(Error::InvalidExpression(format!("{}(*) is not supported", name)));
+            }
+            return Ok(ColumnType::IntType);
+        }
+        if function.args.len() != 1 {
+            return Err(Error::InvalidExpression(format!("{} takes exactly one argument", name)));
+        }
+
+        let arg_type = self.expression_type(&function.args[0])?;
+        match name {
+            "count" => Ok(ColumnType::IntType),
+            "sum" | "avg" => {
+                if !is_type(&arg_type, &ColumnType::IntType) {
+                    return Err(Error::TypeMismatch(format!("{}({})", name, arg_type)));
                 }
-                table.rows.push(row);
-                Ok(true)
+                Ok(ColumnType::IntType)
             },
-            None => Err(ERR_TABLE_DOES_NOT_EXIST.to_string())
+            _ => Ok(arg_type)
         }
     }
 
-    fn select(&self, slct: &SelectStatement) -> Result<Results, String> {
-        match self.tables.get(&slct.from.value) {
-            None => Err(ERR_TABLE_DOES_NOT_EXIST.to_string()),
-            Some(table) => {
-                let mut results: Vec<Vec<Box<dyn Cell>>> = vec!{};
-                let mut columns: Vec<Column> = vec!{};
+    /// Evaluates an aggregate call over a group of rows. NULL arguments are
+    /// skipped, as are repeated ones for DISTINCT, and `avg` truncates toward
+    /// zero since there is no fractional type.
+    fn evaluate_aggregate(&self, rows: &[&[MemoryCell]], function: &FunctionExpression) -> Result<MemoryCell, Error> {
+        self.function_type(function)?;
+        if function.asterisk {
+            return count_cell(rows.len());
+        }
 
-                for row in &table.rows {
-                    let mut result: Vec<Box<dyn Cell>> = vec!{};
+        let arg_type = self.expression_type(&function.args[0])?;
+        let mut values: Vec<MemoryCell> = vec!{};
+        let mut seen: HashSet<MemoryCell> = HashSet::new();
+        for row in rows {
+            let (cell, _) = self.evaluate_cell(row, &function.args[0])?;
+            if cell.is_null() || (function.distinct && !seen.insert(cell.clone())) {
+                continue;
+            }
+            values.push(cell);
+        }
 
-                    for exp in &slct.item {
-                        if exp.kind != ExpressionKind::LiteralKind {
-                            println!("Skipping non-literal expression.");
-                            continue;
-                        }
+        let name = &function.name.value[..];
+        if name == "count" {
+            return count_cell(values.len());
+        }
+        if values.is_empty() {
+            return Ok(MemoryCell::null());
+        }
 
-                        let lit = &exp.literal;
+        match name {
+            "sum" | "avg" => {
+                let mut sum: i32 = 0;
+                for value in &values {
+                    sum = sum.checked_add(value.as_int()?).ok_or(Error::IntegerOverflow)?;
+                }
+                if name == "avg" {
+                    let count: i32 = values.len().try_into().map_err(|_| Error::IntegerOverflow)?;
+                    sum /= count;
+                }
+                Ok(MemoryCell::from_int(sum))
+            },
+            _ => {
+                let wanted = if name == "min" { Ordering::Less } else { Ordering::Greater };
+                let mut best = values[0].clone();
+                for value in &values[1..] {
+                    if value.compare(&best, &arg_type)? == wanted {
+                        best = value.clone();
+                    }
+                }
+                Ok(best)
+            }
+        }
+    }
 
-                        if lit.kind == TokenKind::IdentifierKind {
-                            let mut found = false;
+    /// Type of a window function call, checking its arguments and window.
+    fn window_type(&self, function: &FunctionExpression) -> Result<ColumnType, Error> {
+        let over = function.over.as_ref().unwrap();
+        for operand in function_operands(function) {
+            if contains_window(operand) {
+                return Err(Error::InvalidExpression("window function calls cannot be nested".to_string()));
+            }
+            self.expression_type(operand)?;
+        }
+        if let Some(frame) = &over.frame {
+            for bound in [&frame.start, &frame.end] {
+                if bound.offset.is_none() {
+                    continue;
+                }
+                if frame.units == FrameUnits::RangeUnits {
+                    return Err(Error::InvalidExpression("RANGE with offset PRECEDING or FOLLOWING is not supported".to_string()));
+                }
+                frame_offset(bound)?;
+            }
+        }
 
-                            for (j, col) in table.columns.iter().enumerate() {
-                                if *col == lit.value {
-                                    if columns.len() < table.columns.len() {
-                                        columns.push(Column{
-                                            col_type: table.column_types[j].clone(),
-                                            name: lit.value.clone()
-                                        });
-                                    }
+        let name = &function.name.value[..];
+        match name {
+            "row_number" | "rank" | "dense_rank" => {
+                if function.asterisk || !function.args.is_empty() {
+                    return Err(Error::InvalidExpression(format!("{} takes no arguments", name)));
+                }
+                Ok(ColumnType::IntType)
+            },
+            "lag" | "lead" => {
+                if function.asterisk || function.args.is_empty() || function.args.len() > 3 {
+                    return Err(Error::InvalidExpression(format!("{} takes one to three arguments", name)));
+                }
 
-                                    result.push(Box::new(row[j].clone()) as Box<dyn Cell>);
-                                    found = true;
-                                    break;
-                                }
-                            }
-                            if !found {
-                                return Err(ERR_COLUMN_DOES_NOT_EXIST.to_string());
-                            }
+                let value_type = self.expression_type(&function.args[0])?;
+                if let Some(offset) = function.args.get(1) {
+                    let offset_type = self.expression_type(offset)?;
+                    if !is_type(&offset_type, &ColumnType::IntType) {
+                        return Err(Error::TypeMismatch(format!("{} offset must be int, got {}", name, offset_type)));
+                    }
+                }
+                let default_type = match function.args.get(2) {
+                    Some(default) => self.expression_type(default)?,
+                    None => return Ok(value_type)
+                };
+                if is_type(&default_type, &value_type) {
+                    Ok(value_type)
+                } else if is_type(&value_type, &default_type) {
+                    Ok(default_type)
+                } else {
+                    Err(Error::TypeMismatch(format!("{} default must be {}, got {}", name, value_type, default_type)))
+                }
+            },
+            _ => {
+                if function.distinct {
+                    return Err(Error::InvalidExpression("DISTINCT is not supported for window functions".to_string()));
+                }
+                self.function_type(function)
+            }
+        }
+    }
 
-                            continue;
-                        }
+    /// Records the type of each window function call in `exps`, so that
+    /// rows extended by `evaluate_windows` can be evaluated.
+    fn prepare_windows(&mut self, exps: &[&Expression]) -> Result<(), Error> {
+        let mut windows: Vec<Expression> = vec!{};
+        for exp in exps {
+            collect_windows(exp, &mut windows);
+        }
 
-                        return Err(ERR_COLUMN_DOES_NOT_EXIST.to_string());
-                    }
+        for exp in windows {
+            let col_type = self.window_type(exp.function.as_ref().unwrap())?;
+            self.windows.push((exp, col_type));
+        }
+        Ok(())
+    }
 
-                    results.push(result)
-                }
-                return Ok(Results{
-                    columns,
-                    rows: results
-                });
+    /// Appends the value of each of the table's window function calls to
+    /// every row, which must be all the rows the calls can see.
+    fn evaluate_windows(&self, rows: &mut [Vec<MemoryCell>]) -> Result<(), Error> {
+        for (exp, _) in &self.windows {
+            let cells = self.evaluate_window(rows, exp.function.as_ref().unwrap())?;
+            for (row, cell) in rows.iter_mut().zip(cells) {
+                row.push(cell);
             }
         }
+        Ok(())
     }
-}
\ No newline at end of file
+
+    /// Evaluates a window function call for each row. Rows are split into
+    /// partitions, sorted within each, and rows with equal sort keys are
+    /// peers. Without a frame, an aggregate reads the whole partition, or
+    /// with ORDER BY, the rows up to the current row's last peer.
+    fn evaluate_window(&self, rows: &[Vec<MemoryCell>], function: &FunctionExpression) -> Result<Vec<MemoryCell>, Error> {
+        let over = function.over.as_ref().unwrap();
+        let mut order_by: Vec<(Option<usize>, ColumnType)> = vec!{};
+        for item in &over.order_by {
+            order_by.push((None, self.expression_type(&item.exp)?));
+        }
+
+        // Each partition holds the sort keys and index of its rows.
+        let mut partitions: Vec<Vec<(Vec<MemoryCell>, usize)>> = vec!{};
+        let mut partition_index: HashMap<Vec<MemoryCell>, usize> = HashMap::new();
+        for (r, row) in rows.iter().enumerate() {
+            let mut key: Vec<MemoryCell> = vec!{};
+            for exp in &over.partition_by {
+                key.push(self.evaluate_cell(row, exp)?.0);
+            }
+            let mut keys: Vec<MemoryCell> = vec!{};
+            for item in &over.order_by {
+                keys.push(self.evaluate_cell(row, &item.exp)?.0);
+            }
+
+            let i = *partition_index.entry(key).or_insert_with(|| {
+                partitions.push(vec!{});
+                partitions.len() - 1
+            });
+            partitions[i].push((keys, r));
+        }
+
+        let name = &function.name.value[..];
+        let mut cells = vec!{MemoryCell::null(); rows.len()};
+        for partition in &mut partitions {
+            sort_rows(partition, &over.order_by, &order_by)?;
+
+            // Each row's peer group, numbered from 0, and the positions where
+            // the group starts and ends.
+            let n = partition.len();
+            let mut peer_groups: Vec<usize> = vec!{0; n};
+            let mut peers: Vec<(usize, usize)> = vec!{(0, n); n};
+            for p in 1..n {
+                if compare_keys(&partition[p - 1].0, &partition[p].0, &over.order_by, &order_by)? == Ordering::Equal {
+                    peer_groups[p] = peer_groups[p - 1];
+                    peers[p].0 = peers[p - 1].0;
+                } else {
+                    peer_groups[p] = peer_groups[p - 1] + 1;
+                    peers[p].0 = p;
+                }
+            }
+            for p in (1..n).rev() {
+                peers[p - 1].1 = if peer_groups[p - 1] == peer_groups[p] { peers[p].1 } else { p };
+            }
+
+            for p in 0..n {
+                let row = &rows[partition[p].1];
+                let peers = peers[p];
+                cells[partition[p].1] = match name {
+                    "row_number" => count_cell(p + 1)?,
+                    "rank" => count_cell(peers.0 + 1)?,
+                    "dense_rank" => count_cell(peer_groups[p] + 1)?,
+                    "lag" | "lead" => {
+                        let offset = match function.args.get(1) {
+                            Some(exp) => self.evaluate_cell(row, exp)?.0,
+                            None => MemoryCell::from_int(1)
+                        };
+                        if offset.is_null() {
+                            continue;
+                        }
+
+                        let offset = offset.as_int()? as i64;
+                        let target: Option<usize> = if name == "lag" { p as i64 - offset } else { p as i64 + offset }.try_into().ok();
+                        match target {
+                            Some(q) if q < n => self.evaluate_cell(&rows[partition[q].1], &function.args[0])?.0,
+                            _ => match function.args.get(2) {
+                                Some(default) => self.evaluate_cell(row, default)?.0,
+                                None => MemoryCell::null()
+                            }
+                        }
+                    },
+                    _ => {
+                        let (start, end) = match &over.frame {
+                            Some(frame) => (frame_position(frame, false, p, peers, n)?, frame_position(frame, true, p, peers, n)?),
+                            None if over.order_by.is_empty() => (0, n),
+                            None => (0, peers.1)
+                        };
+                        let frame: Vec<&[MemoryCell]> = partition[start..end.max(start)].iter().map(|(_, r)| &rows[*r][..]).collect();
+                        self.evaluate_aggregate(&frame, function)?
+                    }
+                };
+            }
+        }
+        Ok(cells)
+    }
+
+    fn expression_type(&self, exp: &Expression) -> Result<ColumnType, Error> {
+        if let Some(i) = self.subquery_index(exp) {
+            return Ok(self.subqueries[i].1.clone());
+        }
+        if let Some(i) = self.window_index(exp) {
+            return Ok(self.windows[i].1.clone());
+        }
+
+        match exp.kind {
+            ExpressionKind::SubqueryKind => Err(Error::InvalidExpression("subquery is not allowed here".to_string())),
+            ExpressionKind::FunctionKind => {
+                let function = exp.function.as_ref().unwrap();
+                if function.over.is_some() {
+                    return Err(function_not_allowed(function));
+                }
+                self.function_type(function)
+            },
+            ExpressionKind::LiteralKind => {
+                let lit = exp.literal.as_ref().unwrap();
+                match lit.kind {
+                    TokenKind::IdentifierKind => Ok(self.column_types[self.resolve_column(&exp.table, lit)?].clone()),
+                    TokenKind::NumericKind => Ok(ColumnType::IntType),
+                    TokenKind::StringKind => Ok(ColumnType::TextType),
+                    TokenKind::BooleanKind => Ok(ColumnType::BoolType),
+                    TokenKind::NullKind => Ok(ColumnType::NullType),
+                    _ => Err(Error::InvalidExpression(format!("unexpected token {}", lit.value)))
+                }
+            },
+            ExpressionKind::UnaryKind => {
+                let unary = exp.unary.as_ref().unwrap();
+                let operand_type = self.expression_type(&unary.operand)?;
+                let result_type = match &unary.op.value[..] {
+                    MINUS_SYMBOL => ColumnType::IntType,
+                    NOT_KEYWORD => ColumnType::BoolType,
+                    _ => return Err(unsupported_operator(&unary.op))
+                };
+
+                if !is_type(&operand_type, &result_type) {
+                    return Err(unary_type_mismatch(&unary.op, &operand_type));
+                }
+                Ok(result_type)
+            },
+            ExpressionKind::BinaryKind => {
+                let binary = exp.binary.as_ref().unwrap();
+                let a_type = self.expression_type(&binary.a)?;
+                let b_type = self.expression_type(&binary.b)?;
+                match &binary.op.value[..] {
+                    CONCAT_SYMBOL => Ok(ColumnType::TextType),
+                    IS_KEYWORD => Ok(ColumnType::BoolType),
+                    PLUS_SYMBOL | MINUS_SYMBOL | ASTERISK_SYMBOL | SLASH_SYMBOL => {
+                        if !is_type(&a_type, &ColumnType::IntType) || !is_type(&b_type, &ColumnType::IntType) {
+                            return Err(binary_type_mismatch(&binary.op, &a_type, &b_type));
+                        }
+                        Ok(ColumnType::IntType)
+                    },
+                    AND_KEYWORD | OR_KEYWORD => {
+                        if !is_type(&a_type, &ColumnType::BoolType) || !is_type(&b_type, &ColumnType::BoolType) {
+                            return Err(binary_type_mismatch(&binary.op, &a_type, &b_type));
+                        }
+                        Ok(ColumnType::BoolType)
+                    },
+                    EQ_SYMBOL | NEQ_SYMBOL | LT_SYMBOL | LTE_SYMBOL | GT_SYMBOL | GTE_SYMBOL => {
+                        if !is_type(&a_type, &b_type) && !is_type(&b_type, &a_type) {
+                            return Err(binary_type_mismatch(&binary.op, &a_type, &b_type));
+                        }
+                        Ok(ColumnType::BoolType)
+                    },
+                    _ => Err(unsupported_operator(&binary.op))
+                }
+            }
+        }
+    }
+
+    fn evaluate_literal_cell(&self, row: &[MemoryCell], exp: &Expression) -> Result<(MemoryCell, ColumnType), Error> {
+        let lit = exp.literal.as_ref().unwrap();
+        match lit.kind {
+            TokenKind::IdentifierKind => {
+                let j = self.resolve_column(&exp.table, lit)?;
+                Ok((row[j].clone(), self.column_types[j].clone()))
+            },
+            TokenKind::NumericKind => Ok((MemoryBackend::token_to_cell(lit)?, ColumnType::IntType)),
+            TokenKind::StringKind => Ok((MemoryBackend::token_to_cell(lit)?, ColumnType::TextType)),
+            TokenKind::BooleanKind => Ok((MemoryBackend::token_to_cell(lit)?, ColumnType::BoolType)),
+            TokenKind::NullKind => Ok((MemoryCell::null(), ColumnType::NullType)),
+            _ => Err(Error::InvalidExpression(format!("unexpected token {}", lit.value)))
+        }
+    }
+
+    fn evaluate_unary_cell(&self, row: &[MemoryCell], unary: &UnaryExpression) -> Result<(MemoryCell, ColumnType), Error> {
+        match &unary.op.value[..] {
+            MINUS_SYMBOL => {
+                // A negated number is read as a whole, so that the smallest
+                // int can be written.
+                if let Some(lit) = &unary.operand.literal {
+                    if lit.kind == TokenKind::NumericKind {
+                        let negated = Token{
+                            value: format!("-{}", lit.value),
+                            ..lit.clone()
+                        };
+                        return Ok((MemoryBackend::token_to_cell(&negated)?, ColumnType::IntType));
+                    }
+                }
+
+                let (cell, col_type) = self.evaluate_cell(row, &unary.operand)?;
+                if !is_type(&col_type, &ColumnType::IntType) {
+                    return Err(unary_type_mismatch(&unary.op, &col_type));
+                }
+                if cell.is_null() {
+                    return Ok((cell, ColumnType::IntType));
+                }
+
+                match cell.as_int()?.checked_neg() {
+                    Some(i) => Ok((MemoryCell::from_int(i), ColumnType::IntType)),
+                    None => Err(Error::IntegerOverflow)
+                }
+            },
+            NOT_KEYWORD => {
+                let (cell, col_type) = self.evaluate_cell(row, &unary.operand)?;
+                if !is_type(&col_type, &ColumnType::BoolType) {
+                    return Err(unary_type_mismatch(&unary.op, &col_type));
+                }
+                if cell.is_null() {
+                    return Ok((cell, ColumnType::BoolType));
+                }
+
+                Ok((MemoryCell::from_bool(!cell.as_bool()?), ColumnType::BoolType))
+            },
+            _ => Err(unsupported_operator(&unary.op))
+        }
+    }
+
+    fn evaluate_binary_cell(&self, row: &[MemoryCell], binary: &BinaryExpression) -> Result<(MemoryCell, ColumnType), Error> {
+        let (a, a_type) = self.evaluate_cell(row, &binary.a)?;
+
+        match &binary.op.value[..] {
+            AND_KEYWORD | OR_KEYWORD => {
+                if !is_type(&a_type, &ColumnType::BoolType) {
+                    return Err(binary_type_mismatch(&binary.op, &a_type, &ColumnType::BoolType));
+                }
+
+                // FALSE AND x and TRUE OR x are decided without evaluating x,
+                // otherwise NULL wins unless the right operand decides.
+                let is_and = binary.op.value == AND_KEYWORD;
+                if !a.is_null() && a.as_bool()? != is_and {
+                    return Ok((a, ColumnType::BoolType));
+                }
+
+                let (b, b_type) = self.evaluate_cell(row, &binary.b)?;
+                if !is_type(&b_type, &ColumnType::BoolType) {
+                    return Err(binary_type_mismatch(&binary.op, &a_type, &b_type));
+                }
+                if !b.is_null() && b.as_bool()? != is_and {
+                    return Ok((b, ColumnType::BoolType));
+                }
+                if a.is_null() {
+                    return Ok((a, ColumnType::BoolType));
+                }
+                Ok((b, ColumnType::BoolType))
+            },
+            IS_KEYWORD => Ok((MemoryCell::from_bool(a.is_null()), ColumnType::BoolType)),
+            CONCAT_SYMBOL => {
+                let (b, b_type) = self.evaluate_cell(row, &binary.b)?;
+                if a.is_null() || b.is_null() {
+                    return Ok((MemoryCell::null(), ColumnType::TextType));
+                }
+
+                let s = a.to_text(&a_type)? + &b.to_text(&b_type)?;
+                Ok((MemoryCell::from_text(&s), ColumnType::TextType))
+            },
+            PLUS_SYMBOL | MINUS_SYMBOL | ASTERISK_SYMBOL | SLASH_SYMBOL => {
+                let (b, b_type) = self.evaluate_cell(row, &binary.b)?;
+                if !is_type(&a_type, &ColumnType::IntType) || !is_type(&b_type, &ColumnType::IntType) {
+                    return Err(binary_type_mismatch(&binary.op, &a_type, &b_type));
+                }
+                if a.is_null() || b.is_null() {
+                    return Ok((MemoryCell::null(), ColumnType::IntType));
+                }
+
+                let (a, b) = (a.as_int()?, b.as_int()?);
+                let result = match &binary.op.value[..] {
+                    PLUS_SYMBOL => a.checked_add(b),
+                    MINUS_SYMBOL => a.checked_sub(b),
+                    ASTERISK_SYMBOL => a.checked_mul(b),
+                    _ => {
+                        if b == 0 {
+                            return Err(Error::DivisionByZero);
+                        }
+                        a.checked_div(b)
+                    }
+                };
+
+                match result {
+                    Some(i) => Ok((MemoryCell::from_int(i), ColumnType::IntType)),
+                    None => Err(Error::IntegerOverflow)
+                }
+            },
+            EQ_SYMBOL | NEQ_SYMBOL | LT_SYMBOL | LTE_SYMBOL | GT_SYMBOL | GTE_SYMBOL => {
+                let (b, b_type) = self.evaluate_cell(row, &binary.b)?;
+                if !is_type(&a_type, &b_type) && !is_type(&b_type, &a_type) {
+                    return Err(binary_type_mismatch(&binary.op, &a_type, &b_type));
+                }
+                if a.is_null() || b.is_null() {
+                    return Ok((MemoryCell::null(), ColumnType::BoolType));
+                }
+
+                let ordering = a.compare(&b, &a_type)?;
+                let result = match &binary.op.value[..] {
+                    EQ_SYMBOL => ordering == Ordering::Equal,
+                    NEQ_SYMBOL => ordering != Ordering::Equal,
+                    LT_SYMBOL => ordering == Ordering::Less,
+                    LTE_SYMBOL => ordering != Ordering::Greater,
+                    GT_SYMBOL => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less
+                };
+                Ok((MemoryCell::from_bool(result), ColumnType::BoolType))
+            },
+            _ => Err(unsupported_operator(&binary.op))
+        }
+    }
+
+    fn evaluate_cell(&self, row: &[MemoryCell], exp: &Expression) -> Result<(MemoryCell, ColumnType), Error> {
+        if let Some(i) = self.subquery_index(exp) {
+            return Ok((row[self.columns.len() + self.aggregates.len() + i].clone(), self.subqueries[i].1.clone()));
+        }
+        if let Some(i) = self.window_index(exp) {
+            let j = self.columns.len() + self.aggregates.len() + self.subqueries.len() + i;
+            return Ok((row[j].clone(), self.windows[i].1.clone()));
+        }
+
+        match exp.kind {
+            ExpressionKind::SubqueryKind => Err(Error::InvalidExpression("subquery is not allowed here".to_string())),
+            ExpressionKind::LiteralKind => self.evaluate_literal_cell(row, exp),
+            ExpressionKind::UnaryKind => self.evaluate_unary_cell(row, exp.unary.as_ref().unwrap()),
+            ExpressionKind::BinaryKind => self.evaluate_binary_cell(row, exp.binary.as_ref().unwrap()),
+            ExpressionKind::FunctionKind => match self.aggregates.iter().position(|a| a == exp) {
+                Some(i) => Ok((row[self.columns.len() + i].clone(), self.expression_type(exp)?)),
+                None => Err(function_not_allowed(exp.function.as_ref().unwrap()))
+            }
+        }
+    }
+
+    /// Evaluates the output cells and ORDER BY keys for a row, returned as
+    /// `(keys, result)`.
+    fn project(&self, row: &[MemoryCell], outputs: &[Output<'_>], items: &[OrderByItem], order_by: &[(Option<usize>, ColumnType)]) -> Result<(Vec<MemoryCell>, Vec<MemoryCell>), Error> {
+        let mut result: Vec<MemoryCell> = vec!{};
+        for output in outputs {
+            let cell = match output {
+                Output::ColumnOutput(j) => row[*j].clone(),
+                Output::ExpressionOutput(exp) => self.evaluate_cell(row, exp)?.0
+            };
+            result.push(cell);
+        }
+
+        let mut keys: Vec<MemoryCell> = vec!{};
+        for (item, (output, _)) in items.iter().zip(order_by) {
+            let key = match output {
+                Some(i) => result[*i].clone(),
+                None => self.evaluate_cell(row, &item.exp)?.0
+            };
+            keys.push(key);
+        }
+        Ok((keys, result))
+    }
+
+    /// If `on` is `a = b` with `a` and `b` columns from opposite sides of a
+    /// join whose left side has `split` columns, returns the left and right
+    /// column indexes, the right one relative to the right side.
+    fn equi_join_columns(&self, on: &Expression, split: usize) -> Option<(usize, usize)> {
+        let binary = on.binary.as_ref()?;
+        if binary.op.value != EQ_SYMBOL {
+            return None;
+        }
+
+        let column = |exp: &Expression| match &exp.literal {
+            Some(lit) if lit.kind == TokenKind::IdentifierKind => self.resolve_column(&exp.table, lit).ok(),
+            _ => None
+        };
+        let a = column(&binary.a)?;
+        let b = column(&binary.b)?;
+        if self.column_types[a] != self.column_types[b] {
+            return None;
+        }
+        match (a < split, b < split) {
+            (true, false) => Some((a, b - split)),
+            (false, true) => Some((b, a - split)),
+            _ => None
+        }
+    }
+
+    /// Evaluates a WHERE condition against a single row, treating NULL as false.
+    fn evaluate_predicate(&self, row: &[MemoryCell], exp: &Expression) -> Result<bool, Error> {
+        let (cell, col_type) = self.evaluate_cell(row, exp)?;
+        if !is_type(&col_type, &ColumnType::BoolType) {
+            return Err(Error::TypeMismatch(format!("condition must be boolean, got {}", col_type)));
+        }
+
+        Ok(!cell.is_null() && cell.as_bool()?)
+    }
+}
+
+pub struct MemoryBackend {
+    tables: BTreeMap<String, Table>
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryBackend{
+    pub fn new() -> MemoryBackend {
+        MemoryBackend{
+            tables: BTreeMap::new()
+        }
+    }
+
+    fn token_to_cell(token: &Token) -> Result<MemoryCell, Error> {
+        match token.kind {
+            TokenKind::NumericKind => match token.value.parse::<i32>() {
+                Ok(i) => Ok(MemoryCell::from_int(i)),
+                Err(_) => Err(Error::InvalidValue(format!("{} is not a valid int", token.value)))
+            },
+            TokenKind::StringKind => Ok(MemoryCell::from_text(&token.value)),
+            TokenKind::BooleanKind => Ok(MemoryCell::from_bool(token.value == TRUE_KEYWORD)),
+            TokenKind::NullKind => Ok(MemoryCell::null()),
+            _ => Err(Error::InvalidExpression(format!("unexpected token {}", token.value)))
+        }
+    }
+
+    /// Builds the qualified columns and rows a FROM item reads. Base tables
+    /// are borrowed; joins and subqueries are computed into new rows.
+    /// `names` collects the qualifiers in use so each appears only once.
+    fn source<'a>(&'a self, from: &FromItem, ctes: &'a Ctes, names: &mut Vec<String>) -> Result<(Table, Rows<'a>), Error> {
+        if from.kind != FromItemKind::JoinKind {
+            let qualifier = &from.alias.as_ref().or(from.table.as_ref()).unwrap().value;
+            if names.contains(qualifier) {
+                return Err(Error::InvalidExpression(format!("table name \"{}\" specified more than once", qualifier)));
+            }
+            names.push(qualifier.clone());
+        }
+
+        // Common table expressions shadow stored tables of the same name.
+        if from.kind == FromItemKind::TableKind {
+            let name = from.table.as_ref().unwrap();
+            let table = match ctes.get(&name.value).or_else(|| self.tables.get(&name.value)) {
+                Some(table) => table,
+                None => return Err(Error::TableDoesNotExist(name.value.clone()))
+            };
+            return Ok((table.schema(names.last().unwrap()), Cow::Borrowed(&table.rows)));
+        }
+
+        if from.kind == FromItemKind::SubqueryKind {
+            let (columns, rows) = self.query(from.subquery.as_ref().unwrap(), ctes, None, false)?;
+            let table = Table{
+                columns: columns.iter().map(|c| c.name.clone()).collect(),
+                column_types: columns.iter().map(|c| c.col_type.clone()).collect(),
+                not_null: vec!{false; columns.len()},
+                qualifiers: vec!{names.last().unwrap().clone(); columns.len()},
+                ..Table::new()
+            };
+            return Ok((table, Cow::Owned(rows)));
+        }
+
+        let join = from.join.as_ref().unwrap();
+        let (left, left_rows) = self.source(&join.left, ctes, names)?;
+        let (right, right_rows) = self.source(&join.right, ctes, names)?;
+
+        // Either side of an outer join may be padded with NULLs, so nothing
+        // in the joined table is NOT NULL.
+        let mut table = Table{
+            columns: [&left.columns[..], &right.columns[..]].concat(),
+            column_types: [&left.column_types[..], &right.column_types[..]].concat(),
+            qualifiers: [&left.qualifiers[..], &right.qualifiers[..]].concat(),
+            ..Table::new()
+        };
+        table.not_null = vec!{false; table.columns.len()};
+
+        if let Some(on) = &join.on {
+            let on_type = table.expression_type(on)?;
+            if !is_type(&on_type, &ColumnType::BoolType) {
+                return Err(Error::TypeMismatch(format!("JOIN condition must be boolean, got {}", on_type)));
+            }
+        }
+
+        let padding = vec!{MemoryCell::null(); right.columns.len()};
+        let mut rows: Vec<Vec<MemoryCell>> = vec!{};
+
+        // An equality between a column on each side is joined by hashing
+        // the right side; anything else falls back to a nested loop.
+        if let Some((l, r)) = join.on.as_ref().and_then(|on| table.equi_join_columns(on, left.columns.len())) {
+            let mut index: HashMap<&MemoryCell, Vec<&Vec<MemoryCell>>> = HashMap::new();
+            for row in right_rows.iter() {
+                if !row[r].is_null() {
+                    index.entry(&row[r]).or_default().push(row);
+                }
+            }
+
+            for row in left_rows.iter() {
+                match index.get(&row[l]) {
+                    Some(matches) if !row[l].is_null() => {
+                        for right_row in matches {
+                            rows.push([&row[..], &right_row[..]].concat());
+                        }
+                    },
+                    _ if join.join_type == JoinType::LeftJoin => rows.push([&row[..], &padding[..]].concat()),
+                    _ => ()
+                }
+            }
+        } else {
+            for row in left_rows.iter() {
+                let mut matched = false;
+                for right_row in right_rows.iter() {
+                    let joined = [&row[..], &right_row[..]].concat();
+                    if let Some(on) = &join.on {
+                        if !table.evaluate_predicate(&joined, on)? {
+                            continue;
+                        }
+                    }
+                    matched = true;
+                    rows.push(joined);
+                }
+                if !matched && join.join_type == JoinType::LeftJoin {
+                    rows.push([&row[..], &padding[..]].concat());
+                }
+            }
+        }
+
+        Ok((table, Cow::Owned(rows)))
+    }
+
+    /// Runs a SELECT, returning its columns and rows. A subquery gets the
+    /// schema and current row of its enclosing query as `outer`, whose
+    /// columns it can read when its own do not match. With `types_only` no
+    /// rows are produced, but the statement is still fully checked.
+    fn query(&self, slct: &SelectStatement, ctes: &Ctes, outer: Option<(&Table, &[MemoryCell])>, types_only: bool) -> Result<(Vec<Column>, Vec<Vec<MemoryCell>>), Error> {
+        if let Some(set) = &slct.set_operation {
+            return self.query_set_operation(slct, set, ctes, outer, types_only);
+        }
+
+        // Without FROM, items are evaluated once against a single empty row.
+        let (mut table, mut source_rows) = match &slct.from {
+            Some(from) => self.source(from, ctes, &mut vec!{})?,
+            None => (Table::new(), Cow::Owned(vec!{vec!{}}))
+        };
+        if let Some((outer_table, outer_row)) = outer {
+            table.add_outer(outer_table);
+            source_rows = Cow::Owned(source_rows.iter().map(|row| [&row[..], outer_row].concat()).collect());
+        }
+
+        // GROUP BY, HAVING or any aggregate call collapses the filtered rows
+        // into one row per group.
+        let mut aggregates: Vec<Expression> = vec!{};
+        for exp in slct.item.iter().filter_map(|item| item.exp.as_ref()).chain(&slct.having) {
+            collect_aggregates(exp, &mut aggregates);
+        }
+        for item in &slct.order_by {
+            collect_aggregates(&item.exp, &mut aggregates);
+        }
+        let grouped = !aggregates.is_empty() || !slct.group_by.is_empty() || slct.having.is_some();
+
+        // Window functions are evaluated after grouping and filtering, so
+        // they can only be used in the select list and ORDER BY.
+        let clauses = slct.filter.iter().map(|exp| ("WHERE", exp))
+            .chain(slct.group_by.iter().map(|exp| ("GROUP BY", exp)))
+            .chain(slct.having.iter().map(|exp| ("HAVING", exp)));
+        for (clause, exp) in clauses {
+            if contains_window(exp) {
+                return Err(Error::InvalidExpression(format!("window functions are not allowed in {}", clause)));
+            }
+        }
+        for aggregate in &aggregates {
+            if contains_window(aggregate) {
+                return Err(Error::InvalidExpression("aggregate function calls cannot contain window function calls".to_string()));
+            }
+        }
+
+        // Subqueries read by rows of the source table are evaluated per row,
+        // while those in a grouped query's output are evaluated per group.
+        let mut row_exps: Vec<&Expression> = slct.filter.iter().chain(&slct.group_by).collect();
+        for aggregate in &aggregates {
+            row_exps.extend(&aggregate.function.as_ref().unwrap().args);
+        }
+        let mut output_exps: Vec<&Expression> = slct.item.iter().filter_map(|item| item.exp.as_ref()).chain(&slct.having).collect();
+        output_exps.extend(slct.order_by.iter().map(|item| &item.exp));
+        if !grouped {
+            row_exps.extend(output_exps.iter().copied());
+        }
+        self.prepare_subqueries(&mut table, ctes, row_exps)?;
+
+        let mut group_table = Table{
+            columns: table.columns.clone(),
+            column_types: table.column_types.clone(),
+            qualifiers: table.qualifiers.clone(),
+            depths: table.depths.clone(),
+            aggregates,
+            ..Table::new()
+        };
+        if grouped {
+            // Subqueries in the output of a grouped query are evaluated per
+            // group, so they can only read its grouped columns.
+            group_table.ungrouped = (0..table.columns.len())
+                .map(|j| table.depth(j) == 0 && check_grouped_column(&table, j, &slct.group_by).is_err())
+                .collect();
+            self.prepare_subqueries(&mut group_table, ctes, output_exps.clone())?;
+            group_table.prepare_windows(&output_exps)?;
+        } else {
+            table.prepare_windows(&output_exps)?;
+        }
+        let output_table = if grouped { &group_table } else { &table };
+
+        let mut columns: Vec<Column> = vec!{};
+        let mut outputs: Vec<Output> = vec!{};
+
+        for item in &slct.item {
+            if !item.asterisk {
+                let exp = item.exp.as_ref().unwrap();
+                let mut column = output_table.expression_column(exp)?;
+                if let Some(as_name) = &item.as_name {
+                    column.name = as_name.value.clone();
+                }
+
+                columns.push(column);
+                outputs.push(Output::ExpressionOutput(exp));
+                continue;
+            }
+
+            if let Some(t) = &item.table {
+                if !(0..table.columns.len()).any(|j| table.depth(j) == 0 && table.qualifiers[j] == t.value) {
+                    return Err(Error::TableDoesNotExist(t.value.clone()));
+                }
+            }
+
+            for (j, col) in table.columns.iter().enumerate() {
+                let qualifier = &table.qualifiers[j];
+                if table.depth(j) > 0 {
+                    continue;
+                }
+                if let Some(t) = &item.table {
+                    if t.value != *qualifier {
+                        continue;
+                    }
+                }
+
+                columns.push(Column{
+                    col_type: table.column_types[j].clone(),
+                    name: col.clone(),
+                    table: Some(qualifier.clone())
+                });
+                outputs.push(Output::ColumnOutput(j));
+            }
+        }
+
+        // A bare identifier in ORDER BY refers to an output column first, so
+        // aliases can be sorted on, and a number to an output column's
+        // position; anything else is evaluated on the row.
+        let mut order_by: Vec<(Option<usize>, ColumnType)> = vec!{};
+        for item in &slct.order_by {
+            let output = match &item.exp.literal {
+                Some(lit) if lit.kind == TokenKind::IdentifierKind && item.exp.table.is_none() => columns.iter().position(|c| c.name == lit.value),
+                _ => order_by_position(&item.exp, &columns)?
+            };
+            let col_type = match output {
+                Some(i) => columns[i].col_type.clone(),
+                None => output_table.expression_type(&item.exp)?
+            };
+            order_by.push((output, col_type));
+        }
+
+        let limit = evaluate_count(LIMIT_KEYWORD, slct.limit.as_ref())?;
+        let offset = evaluate_count(OFFSET_KEYWORD, slct.offset.as_ref())?.unwrap_or(0);
+
+        // DISTINCT keeps the first of each set of equal result rows, which is
+        // only well defined if the sort keys are part of the result.
+        if slct.distinct {
+            let selected = |exp: &Expression| outputs.iter().any(|output| match output {
+                Output::ColumnOutput(j) => match &exp.literal {
+                    Some(lit) if lit.kind == TokenKind::IdentifierKind => table.resolve_column(&exp.table, lit).ok() == Some(*j),
+                    _ => false
+                },
+                Output::ExpressionOutput(e) => *e == exp
+            });
+            for (item, (output, _)) in slct.order_by.iter().zip(&order_by) {
+                if output.is_none() && !selected(&item.exp) {
+                    return Err(Error::InvalidExpression("for SELECT DISTINCT, ORDER BY expressions must appear in select list".to_string()));
+                }
+            }
+        }
+
+        if grouped {
+            for exp in &slct.group_by {
+                table.expression_type(exp)?;
+            }
+            for output in &outputs {
+                match output {
+                    Output::ColumnOutput(j) => check_grouped_column(&table, *j, &slct.group_by)?,
+                    Output::ExpressionOutput(exp) => check_grouped(&table, exp, &slct.group_by)?
+                }
+            }
+            if let Some(having) = &slct.having {
+                check_grouped(&table, having, &slct.group_by)?;
+            }
+            for (item, (output, _)) in slct.order_by.iter().zip(&order_by) {
+                if output.is_none() {
+                    check_grouped(&table, &item.exp, &slct.group_by)?;
+                }
+            }
+        }
+        if let Some(filter) = &slct.filter {
+            let filter_type = table.expression_type(filter)?;
+            if !is_type(&filter_type, &ColumnType::BoolType) {
+                return Err(Error::TypeMismatch(format!("condition must be boolean, got {}", filter_type)));
+            }
+        }
+
+        if types_only {
+            return Ok((columns, vec!{}));
+        }
+
+        // Window functions read every selected row, so when there are any,
+        // rows are only projected once all have been selected.
+        let windowed = !output_table.windows.is_empty();
+        let mut selected: Vec<Vec<MemoryCell>> = vec!{};
+
+        let mut seen: HashSet<Vec<MemoryCell>> = HashSet::new();
+        let mut rows: Vec<(Vec<MemoryCell>, Vec<MemoryCell>)> = vec!{};
+        if grouped {
+            let mut filtered: Vec<Cow<[MemoryCell]>> = vec!{};
+            for row in source_rows.iter() {
+                let row = self.extend_row(&table, ctes, row)?;
+                if let Some(filter) = &slct.filter {
+                    if !table.evaluate_predicate(&row, filter)? {
+                        continue;
+                    }
+                }
+                filtered.push(row);
+            }
+
+            // Groups keep the order in which their first row was seen. Without
+            // GROUP BY there is exactly one group, even if it is empty.
+            let mut groups: Vec<Vec<&[MemoryCell]>> = vec!{};
+            let mut group_index: HashMap<Vec<MemoryCell>, usize> = HashMap::new();
+            if slct.group_by.is_empty() {
+                groups.push(vec!{});
+                group_index.insert(vec!{}, 0);
+            }
+
+            for row in &filtered {
+                let mut key: Vec<MemoryCell> = vec!{};
+                for exp in &slct.group_by {
+                    key.push(table.evaluate_cell(row, exp)?.0);
+                }
+
+                let i = *group_index.entry(key).or_insert_with(|| {
+                    groups.push(vec!{});
+                    groups.len() - 1
+                });
+                groups[i].push(row);
+            }
+
+            for group in &groups {
+                // Grouped columns are equal across the group, so they are
+                // read from its first row. Outer columns are the same for
+                // every row, including those of an empty group.
+                let mut group_row = match group.first() {
+                    Some(row) => row[..table.columns.len()].to_vec(),
+                    None => {
+                        let outer_row = outer.map_or(&[][..], |(_, row)| row);
+                        [&vec!{MemoryCell::null(); table.columns.len() - outer_row.len()}[..], outer_row].concat()
+                    }
+                };
+                for aggregate in &group_table.aggregates {
+                    group_row.push(table.evaluate_aggregate(group, aggregate.function.as_ref().unwrap())?);
+                }
+                let group_row = self.extend_row(&group_table, ctes, &group_row)?;
+
+                if let Some(having) = &slct.having {
+                    if !group_table.evaluate_predicate(&group_row, having)? {
+                        continue;
+                    }
+                }
+                if windowed {
+                    selected.push(group_row.into_owned());
+                    continue;
+                }
+                let (keys, result) = group_table.project(&group_row, &outputs, &slct.order_by, &order_by)?;
+                if slct.distinct && !seen.insert(result.clone()) {
+                    continue;
+                }
+                rows.push((keys, result));
+            }
+        } else {
+            for row in source_rows.iter() {
+                // Without ORDER BY the first matching rows are the final ones.
+                if let Some(limit) = limit {
+                    if order_by.is_empty() && rows.len() >= offset.saturating_add(limit) {
+                        break;
+                    }
+                }
+
+                let row = self.extend_row(&table, ctes, row)?;
+                if let Some(filter) = &slct.filter {
+                    if !table.evaluate_predicate(&row, filter)? {
+                        continue;
+                    }
+                }
+                if windowed {
+                    selected.push(row.into_owned());
+                    continue;
+                }
+
+                let (keys, result) = table.project(&row, &outputs, &slct.order_by, &order_by)?;
+                if slct.distinct && !seen.insert(result.clone()) {
+                    continue;
+                }
+                rows.push((keys, result));
+            }
+        }
+
+        if windowed {
+            output_table.evaluate_windows(&mut selected)?;
+            for row in &selected {
+                let (keys, result) = output_table.project(row, &outputs, &slct.order_by, &order_by)?;
+                if slct.distinct && !seen.insert(result.clone()) {
+                    continue;
+                }
+                rows.push((keys, result));
+            }
+        }
+
+        sort_rows(&mut rows, &slct.order_by, &order_by)?;
+        let rows = rows.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).map(|(_, result)| result).collect();
+        Ok((columns, rows))
+    }
+
+    /// Runs both sides of a compound query and combines their rows, then
+    /// applies its ORDER BY, LIMIT and OFFSET. Without ALL each distinct row
+    /// is returned once; with ALL, INTERSECT and EXCEPT match rows of the
+    /// two sides one for one.
+    fn query_set_operation(&self, slct: &SelectStatement, set: &SetOperation, ctes: &Ctes, outer: Option<(&Table, &[MemoryCell])>, types_only: bool) -> Result<(Vec<Column>, Vec<Vec<MemoryCell>>), Error> {
+        let name = match set.op {
+            SetOperator::UnionOperator => "UNION",
+            SetOperator::IntersectOperator => "INTERSECT",
+            SetOperator::ExceptOperator => "EXCEPT"
+        };
+
+        let (mut columns, left_rows) = self.query(&set.left, ctes, outer, types_only)?;
+        let (right_columns, right_rows) = self.query(&set.right, ctes, outer, types_only)?;
+        if columns.len() != right_columns.len() {
+            return Err(Error::InvalidExpression(format!("each {} query must have the same number of columns", name)));
+        }
+        for (column, right) in columns.iter_mut().zip(&right_columns) {
+            if !is_type(&column.col_type, &right.col_type) && !is_type(&right.col_type, &column.col_type) {
+                return Err(Error::TypeMismatch(format!("{} types {} and {} cannot be matched", name, column.col_type, right.col_type)));
+            }
+            if column.col_type == ColumnType::NullType {
+                column.col_type = right.col_type.clone();
+            }
+            column.table = None;
+        }
+
+        // ORDER BY can only read the columns of the result.
+        let table = Table{
+            columns: columns.iter().map(|c| c.name.clone()).collect(),
+            column_types: columns.iter().map(|c| c.col_type.clone()).collect(),
+            ..Table::new()
+        };
+        let outputs: Vec<Output> = (0..columns.len()).map(Output::ColumnOutput).collect();
+        let mut order_by: Vec<(Option<usize>, ColumnType)> = vec!{};
+        for item in &slct.order_by {
+            let order = match order_by_position(&item.exp, &columns)? {
+                Some(i) => (Some(i), columns[i].col_type.clone()),
+                None => (None, table.expression_type(&item.exp)?)
+            };
+            order_by.push(order);
+        }
+
+        let limit = evaluate_count(LIMIT_KEYWORD, slct.limit.as_ref())?;
+        let offset = evaluate_count(OFFSET_KEYWORD, slct.offset.as_ref())?.unwrap_or(0);
+
+        if types_only {
+            return Ok((columns, vec!{}));
+        }
+
+        let mut seen: HashSet<Vec<MemoryCell>> = HashSet::new();
+        let mut result_rows: Vec<Vec<MemoryCell>> = vec!{};
+        if set.op == SetOperator::UnionOperator {
+            for row in left_rows.into_iter().chain(right_rows) {
+                if set.all || seen.insert(row.clone()) {
+                    result_rows.push(row);
+                }
+            }
+        } else {
+            let mut counts: HashMap<Vec<MemoryCell>, usize> = HashMap::new();
+            for row in right_rows {
+                *counts.entry(row).or_insert(0) += 1;
+            }
+
+            for row in left_rows {
+                let count = counts.get(&row).copied().unwrap_or(0);
+                let keep = (set.op == SetOperator::IntersectOperator) == (count > 0);
+                if set.all {
+                    if count > 0 {
+                        *counts.get_mut(&row).unwrap() -= 1;
+                    }
+                    if keep {
+                        result_rows.push(row);
+                    }
+                } else if keep && seen.insert(row.clone()) {
+                    result_rows.push(row);
+                }
+            }
+        }
+
+        let mut rows: Vec<(Vec<MemoryCell>, Vec<MemoryCell>)> = vec!{};
+        for row in result_rows {
+            let (keys, _) = table.project(&row, &outputs, &slct.order_by, &order_by)?;
+            rows.push((keys, row));
+        }
+        sort_rows(&mut rows, &slct.order_by, &order_by)?;
+
+        let rows = rows.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).map(|(_, result)| result).collect();
+        Ok((columns, rows))
+    }
+
+    /// Runs a common table expression into a temporary table. A recursive
+    /// one must be a UNION [ALL] of a query that does not read it and one
+    /// that does; the latter is rerun on the rows added by the previous run
+    /// until it adds none.
+    fn materialize(&self, cte: &CommonTableExpression, recursive: bool, ctes: &Ctes) -> Result<Table, Error> {
+        let name = &cte.name.value;
+        let set = match &cte.query.set_operation {
+            Some(set) if recursive && reads_table(&cte.query, name) => set,
+            _ => {
+                let (columns, rows) = self.query(&cte.query, ctes, None, false)?;
+                let mut table = cte_table(cte, &columns)?;
+                table.rows = rows;
+                return Ok(table);
+            }
+        };
+
+        if set.op != SetOperator::UnionOperator || reads_table(&set.left, name) {
+            return Err(Error::InvalidExpression(format!(
+                "recursive query \"{}\" must be a UNION of a non-recursive term and a recursive term",
+                name
+            )));
+        }
+        if !cte.query.order_by.is_empty() || cte.query.limit.is_some() || cte.query.offset.is_some() {
+            return Err(Error::InvalidExpression(format!(
+                "ORDER BY, LIMIT and OFFSET are not supported in recursive query \"{}\"",
+                name
+            )));
+        }
+
+        let (columns, anchor_rows) = self.query(&set.left, ctes, None, false)?;
+        let mut table = cte_table(cte, &columns)?;
+
+        let mut seen: HashSet<Vec<MemoryCell>> = HashSet::new();
+        let mut working: Vec<Vec<MemoryCell>> = vec!{};
+        for row in anchor_rows {
+            if set.all || seen.insert(row.clone()) {
+                working.push(row);
+            }
+        }
+
+        let mut scope = ctes.clone();
+        while !working.is_empty() {
+            table.rows.extend_from_slice(&working);
+            scope.insert(name.clone(), Table{
+                columns: table.columns.clone(),
+                column_types: table.column_types.clone(),
+                not_null: table.not_null.clone(),
+                rows: working,
+                ..Table::new()
+            });
+
+            let (right_columns, rows) = self.query(&set.right, &scope, None, false)?;
+            if right_columns.len() != columns.len() {
+                return Err(Error::InvalidExpression("each UNION query must have the same number of columns".to_string()));
+            }
+            for (j, column) in right_columns.iter().enumerate() {
+                if !is_type(&column.col_type, &table.column_types[j]) {
+                    return Err(Error::TypeMismatch(format!(
+                        "recursive query \"{}\" column {} has type {} in its non-recursive term but type {} overall",
+                        name,
+                        j + 1,
+                        table.column_types[j],
+                        column.col_type
+                    )));
+                }
+            }
+
+            working = vec!{};
+            for row in rows {
+                if set.all || seen.insert(row.clone()) {
+                    working.push(row);
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Records the type of each subquery in `exps` on `table`, so that rows
+    /// extended by `extend_row` can be evaluated.
+    fn prepare_subqueries(&self, table: &mut Table, ctes: &Ctes, exps: Vec<&Expression>) -> Result<(), Error> {
+        let mut subqueries: Vec<Expression> = vec!{};
+        for exp in exps {
+            collect_subqueries(exp, &mut subqueries);
+        }
+
+        for exp in subqueries {
+            if table.subquery_index(&exp).is_some() {
+                continue;
+            }
+
+            let nulls = vec!{MemoryCell::null(); table.columns.len()};
+            let outer = Some((&*table, &nulls[..]));
+            let sub = subquery_statement(&exp);
+            let (columns, _) = self.query(sub, ctes, outer, true)?;
+            let col_type = match exp.kind {
+                ExpressionKind::UnaryKind => ColumnType::BoolType,
+                _ if columns.len() != 1 => {
+                    return Err(Error::InvalidExpression("subquery must return only one column".to_string()));
+                },
+                ExpressionKind::SubqueryKind => columns[0].col_type.clone(),
+                _ => {
+                    let binary = exp.binary.as_ref().unwrap();
+                    let a_type = table.expression_type(&binary.a)?;
+                    let b_type = &columns[0].col_type;
+                    if !is_type(&a_type, b_type) && !is_type(b_type, &a_type) {
+                        return Err(binary_type_mismatch(&binary.op, &a_type, b_type));
+                    }
+                    ColumnType::BoolType
+                }
+            };
+
+            // A subquery that still resolves without the enclosing query
+            // reads none of its columns, so its rows are the same for all.
+            let uncorrelated = self.query(sub, ctes, None, true).is_ok();
+            table.subqueries.push((exp, col_type));
+            table.subquery_rows.push(if uncorrelated { Some(OnceCell::new()) } else { None });
+        }
+        Ok(())
+    }
+
+    /// Appends the value of each of `table`'s subqueries to `row`.
+    fn extend_row<'a>(&self, table: &Table, ctes: &Ctes, row: &'a [MemoryCell]) -> Result<Cow<'a, [MemoryCell]>, Error> {
+        if table.subqueries.is_empty() {
+            return Ok(Cow::Borrowed(row));
+        }
+
+        let mut row = row.to_vec();
+        for i in 0..table.subqueries.len() {
+            let cell = self.evaluate_subquery(table, ctes, &row, i)?;
+            row.push(cell);
+        }
+        Ok(Cow::Owned(row))
+    }
+
+    /// Runs subquery `i` of `table` against a row. An uncorrelated subquery
+    /// is only run the first time, and its rows are reused after that.
+    fn subquery_rows<'a>(&self, table: &'a Table, ctes: &Ctes, row: &[MemoryCell], i: usize) -> Result<Cow<'a, [Vec<MemoryCell>]>, Error> {
+        let sub = subquery_statement(&table.subqueries[i].0);
+        let outer = Some((table, &row[..table.columns.len()]));
+        let cached = match &table.subquery_rows[i] {
+            Some(cached) => cached,
+            None => return Ok(Cow::Owned(self.query(sub, ctes, outer, false)?.1))
+        };
+
+        if cached.get().is_none() {
+            let (_, rows) = self.query(sub, ctes, outer, false)?;
+            let _ = cached.set(rows);
+        }
+        Ok(Cow::Borrowed(cached.get().unwrap()))
+    }
+
+    /// Evaluates subquery `i` of `table`, a scalar subquery, EXISTS or IN,
+    /// against a row. IN follows the usual NULL rules: it is NULL rather
+    /// than false if no value matched but either side was NULL.
+    fn evaluate_subquery(&self, table: &Table, ctes: &Ctes, row: &[MemoryCell], i: usize) -> Result<MemoryCell, Error> {
+        let exp = &table.subqueries[i].0;
+        let rows = self.subquery_rows(table, ctes, row, i)?;
+        match exp.kind {
+            ExpressionKind::SubqueryKind => match rows.len() {
+                0 => Ok(MemoryCell::null()),
+                1 => Ok(rows[0][0].clone()),
+                _ => Err(Error::InvalidValue("more than one row returned by a subquery used as an expression".to_string()))
+            },
+            ExpressionKind::UnaryKind => Ok(MemoryCell::from_bool(!rows.is_empty())),
+            _ => {
+                let binary = exp.binary.as_ref().unwrap();
+                let (a, _) = table.evaluate_cell(row, &binary.a)?;
+
+                let mut result = MemoryCell::from_bool(false);
+                for value in rows.iter().map(|row| &row[0]) {
+                    if a.is_null() || value.is_null() {
+                        result = MemoryCell::null();
+                    } else if *value == a {
+                        return Ok(MemoryCell::from_bool(true));
+                    }
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn create_table(&mut self, crt: &CreateTableStatement) -> Result<bool, Error> {
+        if self.tables.contains_key(&crt.name.value) {
+            if crt.if_not_exists {
+                return Ok(false);
+            }
+            return Err(Error::TableAlreadyExists(crt.name.value.clone()));
+        }
+
+        let mut table = Table::new();
+
+        for column in &crt.cols {
+            table.add_column(column)?;
+        }
+
+        self.tables.insert(crt.name.value.clone(), table);
+        
+        Ok(true)
+    }
+
+    fn drop_table(&mut self, drp: &DropTableStatement) -> Result<bool, Error> {
+        match self.tables.remove(&drp.name.value) {
+            Some(_) => Ok(true),
+            None if drp.if_exists => Ok(false),
+            None => Err(Error::TableDoesNotExist(drp.name.value.clone()))
+        }
+    }
+
+    fn alter_table(&mut self, alt: &AlterTableStatement) -> Result<bool, Error> {
+        let table = match self.tables.get_mut(&alt.table.value) {
+            Some(table) => table,
+            None => return Err(Error::TableDoesNotExist(alt.table.value.clone()))
+        };
+
+        match alt.kind {
+            AlterTableKind::AddColumnKind => table.add_column(alt.column_definition.as_ref().unwrap())?,
+            AlterTableKind::DropColumnKind => table.drop_column(alt.column.as_ref().unwrap())?,
+            AlterTableKind::RenameColumnKind => table.rename_column(alt.column.as_ref().unwrap(), alt.new_name.as_ref().unwrap())?,
+            AlterTableKind::RenameTableKind => {
+                let new_name = &alt.new_name.as_ref().unwrap().value;
+                if self.tables.contains_key(new_name) {
+                    return Err(Error::TableAlreadyExists(new_name.clone()));
+                }
+
+                let table = self.tables.remove(&alt.table.value).unwrap();
+                self.tables.insert(new_name.clone(), table);
+            }
+        }
+        Ok(true)
+    }
+
+    fn insert(&mut self, inst: &InsertStatement) -> Result<bool, Error> { 
+        let table = match self.tables.get_mut(&inst.table.value) {
+            Some(table) => table,
+            None => return Err(Error::TableDoesNotExist(inst.table.value.clone()))
+        };
+
+        // Map each value position to the table column it fills.
+        let targets: Vec<usize> = match &inst.columns {
+            Some(columns) => table.target_columns(columns.iter().collect())?,
+            None => (0..table.columns.len()).collect()
+        };
+
+        // Rows are built up front so a bad row leaves the table untouched.
+        let mut rows: Vec<Vec<MemoryCell>> = vec!{};
+        for values in &inst.values {
+            if values.len() != targets.len() {
+                return Err(Error::ArityMismatch{
+                    expected: targets.len(),
+                    got: values.len()
+                });
+            }
+
+            let mut row: Vec<MemoryCell> = vec!{MemoryCell::null(); table.columns.len()};
+
+            for (value, &j) in values.iter().zip(&targets) {
+                let (cell, cell_type) = Table::new().evaluate_cell(&[], value)?;
+                table.check_type(j, &cell, &cell_type)?;
+                row[j] = cell;
+            }
+
+            table.check_not_null(&row)?;
+            rows.push(row);
+        }
+
+        table.rows.append(&mut rows);
+        Ok(true)
+    }
+
+    fn update(&mut self, upd: &UpdateStatement) -> Result<usize, Error> {
+        let table = match self.tables.get(&upd.table.value) {
+            Some(table) => table,
+            None => return Err(Error::TableDoesNotExist(upd.table.value.clone()))
+        };
+        let ctes = &Ctes::new();
+
+        let targets = table.target_columns(upd.set.iter().map(|a| &a.column).collect())?;
+        let mut schema = table.schema(&upd.table.value);
+        self.prepare_subqueries(&mut schema, ctes, upd.filter.iter().chain(upd.set.iter().map(|a| &a.value)).collect())?;
+
+        // Assignments see the row as it was before the update, and nothing is
+        // written until every matching row has been computed.
+        let mut updates: Vec<(usize, Vec<MemoryCell>)> = vec!{};
+        for (i, row) in table.rows.iter().enumerate() {
+            let row = self.extend_row(&schema, ctes, row)?;
+            if let Some(filter) = &upd.filter {
+                if !schema.evaluate_predicate(&row, filter)? {
+                    continue;
+                }
+            }
+
+            let mut new_row = row[..table.columns.len()].to_vec();
+            for (assignment, &j) in upd.set.iter().zip(&targets) {
+                let (cell, cell_type) = schema.evaluate_cell(&row, &assignment.value)?;
+                table.check_type(j, &cell, &cell_type)?;
+                new_row[j] = cell;
+            }
+
+            table.check_not_null(&new_row)?;
+            updates.push((i, new_row));
+        }
+
+        let table = self.tables.get_mut(&upd.table.value).unwrap();
+        let count = updates.len();
+        for (i, row) in updates {
+            table.rows[i] = row;
+        }
+        Ok(count)
+    }
+
+    fn delete(&mut self, del: &DeleteStatement) -> Result<usize, Error> {
+        let table = match self.tables.get(&del.table.value) {
+            Some(table) => table,
+            None => return Err(Error::TableDoesNotExist(del.table.value.clone()))
+        };
+        let ctes = &Ctes::new();
+
+        // Decide every row first so an evaluation error deletes nothing.
+        let mut schema = table.schema(&del.table.value);
+        self.prepare_subqueries(&mut schema, ctes, del.filter.iter().collect())?;
+        let mut keep: Vec<bool> = vec!{};
+        for row in &table.rows {
+            let matched = match &del.filter {
+                Some(filter) => schema.evaluate_predicate(&self.extend_row(&schema, ctes, row)?, filter)?,
+                None => true
+            };
+            keep.push(!matched);
+        }
+
+        let table = self.tables.get_mut(&del.table.value).unwrap();
+        let count = keep.iter().filter(|k| !**k).count();
+        let mut keep = keep.into_iter();
+        table.rows.retain(|_| keep.next().unwrap());
+        Ok(count)
+    }
+
+    fn select(&self, qry: &QueryStatement) -> Result<Results, Error> {
+        // Each CTE can read those before it.
+        let mut ctes = Ctes::new();
+        for cte in &qry.with {
+            if ctes.contains_key(&cte.name.value) {
+                return Err(Error::InvalidExpression(format!("WITH query name \"{}\" specified more than once", cte.name.value)));
+            }
+            let table = self.materialize(cte, qry.recursive, &ctes)?;
+            ctes.insert(cte.name.value.clone(), table);
+        }
+
+        let (columns, rows) = self.query(&qry.select, &ctes, None, false)?;
+        Ok(Results{
+            columns,
+            rows: rows.into_iter().map(|row| row.into_iter().map(|cell| Box::new(cell) as Box<dyn Cell>).collect()).collect()
+        })
+    }
+}
@@ -1,6 +1,6 @@
-
 use crate::lexer::*;
 use crate::ast::*;
+use crate::error::Error;
 
 fn token_from_keyword(k: Keyword) -> Token {
     Token {
@@ -18,31 +18,41 @@ fn token_from_symbol(s: Symbol) -> Token {
     }
 }
 
-fn expect_token(tokens: &Vec<Token>, cursor: usize, t: Token) -> bool {
+fn token_from_identifier(id: &str) -> Token {
+    Token {
+        kind: TokenKind::IdentifierKind,
+        value: id.to_string(),
+        loc: Location::new()
+    }
+}
+
+fn expect_token(tokens: &[Token], cursor: usize, t: Token) -> bool {
     if cursor >= tokens.len() {
         return false
     }
     t == tokens[cursor]
 }
 
-fn help_message(tokens: &Vec<Token>, cursor: usize, msg: String) {
-    let c: &Token;
-    if cursor < tokens.len() {
-        c = &tokens[cursor];
+fn help_message(tokens: &[Token], cursor: usize, msg: String) -> Error {
+    let c = if cursor < tokens.len() {
+        &tokens[cursor]
     } else {
-        c = &tokens[cursor - 1];
+        &tokens[cursor - 1]
+    };
+    Error::ParseError{
+        msg: format!("{}, got: {}", msg, c.value),
+        loc: c.loc.clone()
     }
-    println!("[{},{}]: {}, got: {}", c.loc.line, c.loc.col, msg, c.value);
 }
 
-pub fn parse(source: &str) -> Result<Ast, String> {
-    let tokens = lex(&source)?;
+pub fn parse(source: &str) -> Result<Ast, Error> {
+    let tokens = lex(source)?;
     let mut a = Ast{statements: vec!{}};
 
     let mut cursor = 0;
 
     while cursor < tokens.len() {
-        if let(statement, new_cursor, true) = parse_statement(&tokens, cursor, token_from_symbol(SEMICOLON_SYMBOL)){
+        if let(statement, new_cursor, true) = parse_statement(&tokens, cursor, token_from_symbol(SEMICOLON_SYMBOL))?{
             cursor = new_cursor;
             a.statements.push(statement.unwrap());
 
@@ -55,292 +65,1721 @@ pub fn parse(source: &str) -> Result<Ast, String> {
                 } else{
                     break
                 }
-            } 
+            }
 
             if !at_least_one_semicolon {
-                help_message(&tokens, cursor, "Expected semi-colon delimiter between statements".to_string());
-                return Err("Missing semi-colon between statements".to_string());
+                return Err(help_message(&tokens, cursor, "Expected semi-colon delimiter between statements".to_string()));
             }
         } else{
-            help_message(&tokens, cursor, "Expected statement".to_string());
-            return Err("Failed to parse, expected statement".to_string());
+            return Err(help_message(&tokens, cursor, "Expected statement".to_string()));
         }
     }
     Ok(a)
 }
 
-fn parse_statement(tokens: &Vec<Token>, initial_cursor: usize, _delimiter: Token) -> (Option<Statement>, usize, bool) {
+fn parse_statement(tokens: &[Token], initial_cursor: usize, _delimiter: Token) -> Result<(Option<Statement>, usize, bool), Error> {
     let cursor = initial_cursor;
-    let _test = vec![1,2,3,4];
 
     let semicolon_token = token_from_symbol(SEMICOLON_SYMBOL);
 
-    if let(select, new_cursor, true) = parse_select_statement(tokens, cursor, &semicolon_token){
-        return (Some(Statement{
+    if let(select, new_cursor, true) = parse_query_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
             kind: AstKind::SelectKind,
             select_statement: select,
             create_table_statement: None,
             insert_statement: None,
-        }), new_cursor, true);
+            update_statement: None,
+            delete_statement: None,
+            drop_table_statement: None,
+            alter_table_statement: None,
+        }), new_cursor, true));
     }
 
-    if let(insert, new_cursor, true) = parse_insert_statement(tokens, cursor, &semicolon_token){
-        return (Some(Statement{
+    if let(insert, new_cursor, true) = parse_insert_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
             kind: AstKind::InsertKind,
             select_statement: None,
             create_table_statement: None,
-            insert_statement: insert
-        }), new_cursor, true);
+            insert_statement: insert,
+            update_statement: None,
+            delete_statement: None,
+            drop_table_statement: None,
+            alter_table_statement: None,
+        }), new_cursor, true));
     }
 
-    if let(create_table, new_cursor, true) = parse_create_table_statement(tokens, cursor, &semicolon_token){
-        return (Some(Statement{
+    if let(create_table, new_cursor, true) = parse_create_table_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
             kind: AstKind::CreateTableKind,
             select_statement: None,
             create_table_statement: create_table,
-            insert_statement: None
-        }), new_cursor, true);
+            insert_statement: None,
+            update_statement: None,
+            delete_statement: None,
+            drop_table_statement: None,
+            alter_table_statement: None,
+        }), new_cursor, true));
     }
 
-    (None, initial_cursor, false)
-}
-
-fn parse_select_statement(tokens: &Vec<Token>, initial_cursor: usize, delimiter: &Token) -> (Option<SelectStatement>, usize, bool){
-    let mut cursor = initial_cursor;
-
-    if !expect_token(&tokens, cursor, token_from_keyword(SELECT_KEYWORD)){
-        return (None, initial_cursor, false);
+    if let(update, new_cursor, true) = parse_update_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
+            kind: AstKind::UpdateKind,
+            select_statement: None,
+            create_table_statement: None,
+            insert_statement: None,
+            update_statement: update,
+            delete_statement: None,
+            drop_table_statement: None,
+            alter_table_statement: None,
+        }), new_cursor, true));
     }
-    cursor += 1;
-
-    let mut select = SelectStatement{
-        item: vec!{},
-        from: Token::new()
-    };
-
-    if let(expressions, new_cursor, true) = parse_expressions(&tokens, cursor, vec!{&token_from_keyword(FROM_KEYWORD), delimiter}){
-        select.item = expressions.unwrap();
-        cursor = new_cursor;
-
-        if expect_token(tokens, cursor, token_from_keyword(FROM_KEYWORD)){
-            cursor += 1;
 
-            if let(from, new_cursor, true) = parse_token(&tokens, cursor, TokenKind::IdentifierKind){
-                select.from = from.unwrap().clone();
-                cursor = new_cursor;
-            } else{
-                help_message(&tokens, cursor, "Expected FROM token".to_string());
-                return (None, initial_cursor, false);
-            }
-        }
-    } else{
-        return (None, initial_cursor, false);
+    if let(delete, new_cursor, true) = parse_delete_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
+            kind: AstKind::DeleteKind,
+            select_statement: None,
+            create_table_statement: None,
+            insert_statement: None,
+            update_statement: None,
+            delete_statement: delete,
+            drop_table_statement: None,
+            alter_table_statement: None,
+        }), new_cursor, true));
     }
-    return (Some(select), cursor, true);
-}
-
-fn parse_token(tokens: &Vec<Token>, initial_cursor: usize, kind: TokenKind) -> (Option<&Token>, usize, bool){
-    let cursor = initial_cursor;
 
-    if cursor >= tokens.len() {
-        return (None, initial_cursor, false);
+    if let(drop_table, new_cursor, true) = parse_drop_table_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
+            kind: AstKind::DropTableKind,
+            select_statement: None,
+            create_table_statement: None,
+            insert_statement: None,
+            update_statement: None,
+            delete_statement: None,
+            drop_table_statement: drop_table,
+            alter_table_statement: None,
+        }), new_cursor, true));
     }
 
-    let current = &tokens[cursor];
-    if current.kind == kind {
-        return (Some(current), cursor + 1, true);
+    if let(alter_table, new_cursor, true) = parse_alter_table_statement(tokens, cursor, &semicolon_token)?{
+        return Ok((Some(Statement{
+            kind: AstKind::AlterTableKind,
+            select_statement: None,
+            create_table_statement: None,
+            insert_statement: None,
+            update_statement: None,
+            delete_statement: None,
+            drop_table_statement: None,
+            alter_table_statement: alter_table,
+        }), new_cursor, true));
     }
-    return (None, initial_cursor, false);
+
+    Ok((None, initial_cursor, false))
 }
 
-fn parse_expressions(tokens: &Vec<Token>, initial_cursor: usize, delimiters: Vec<&Token>) -> (Option<Vec<Expression>>, usize, bool){
+/// Parses a SELECT, optionally preceded by a WITH clause.
+fn parse_query_statement(tokens: &[Token], initial_cursor: usize, delimiter: &Token) -> Result<(Option<QueryStatement>, usize, bool), Error> {
     let mut cursor = initial_cursor;
 
-    let mut expressions:Vec<Expression> = vec!{};
-    'outer: loop{
-        if cursor >= tokens.len() {
-            return (None, initial_cursor, false);
+    let mut query = QueryStatement{
+        recursive: false,
+        with: vec!{},
+        select: SelectStatement{
+            distinct: false,
+            item: vec!{},
+            from: None,
+            filter: None,
+            group_by: vec!{},
+            having: None,
+            order_by: vec!{},
+            limit: None,
+            offset: None,
+            set_operation: None
         }
+    };
 
-        let current = &tokens[cursor];
+    if expect_token(tokens, cursor, token_from_keyword(WITH_KEYWORD)) {
+        cursor += 1;
 
-        for delimiter in &delimiters {
-            if *delimiter == current {
-                break 'outer;
-            }
+        if expect_token(tokens, cursor, token_from_keyword(RECURSIVE_KEYWORD)) {
+            cursor += 1;
+            query.recursive = true;
         }
 
-        if expressions.len() > 0 {
-            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
-                help_message(tokens, cursor, "Expected comma".to_string());
-                return (None, initial_cursor, false);
+        loop {
+            if !query.with.is_empty() {
+                if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                    break;
+                }
+                cursor += 1;
             }
-            cursor += 1;
-        }
 
-        if let(expression, new_cursor, true) = parse_expression(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+            let (cte, new_cursor, _) = parse_common_table_expression(tokens, cursor)?;
+            query.with.push(cte.unwrap());
             cursor = new_cursor;
-            expressions.push(expression.unwrap());
-        } else{
-            help_message(tokens, cursor, "Expected expression".to_string());
-            return (None, initial_cursor, false);
         }
     }
-    (Some(expressions), cursor, true)
-}
-
-fn parse_expression(tokens: &Vec<Token>, initial_cursor: usize, _: Token) -> (Option<Expression>, usize, bool) {
-    let cursor = initial_cursor;
 
-    let kinds = vec!{TokenKind::IdentifierKind, TokenKind::NumericKind, TokenKind::StringKind};
-
-    for kind in kinds {
-        if let(t, new_cursor, true) = parse_token(tokens, cursor, kind) {
-            return (Some(Expression{
-                literal: t.unwrap().clone(),
-                kind: ExpressionKind::LiteralKind
-            }), new_cursor, true);
+    let (select, new_cursor, ok) = parse_select_statement(tokens, cursor, delimiter)?;
+    if !ok {
+        if !query.with.is_empty() {
+            return Err(help_message(tokens, cursor, "Expected SELECT".to_string()));
         }
+        return Ok((None, initial_cursor, false));
     }
-    return (None, initial_cursor, false);
+    query.select = select.unwrap();
+    cursor = new_cursor;
+
+    Ok((Some(query), cursor, true))
 }
-fn parse_insert_statement(tokens: &Vec<Token>, initial_cursor: usize, _delimiter: &Token) -> (Option<InsertStatement>, usize, bool){
+
+fn parse_common_table_expression(tokens: &[Token], initial_cursor: usize) -> Result<(Option<CommonTableExpression>, usize, bool), Error> {
     let mut cursor = initial_cursor;
 
-    if !expect_token(tokens, cursor, token_from_keyword(INSERT_KEYWORD)){
-        return (None, initial_cursor, false);
+    let (name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected WITH query name".to_string()));
     }
-    cursor += 1;
+    cursor = new_cursor;
 
-    if !expect_token(tokens, cursor, token_from_keyword(INTO_KEYWORD)){
-        help_message(tokens, cursor, "Expected into".to_string());
-        return (None, initial_cursor, false);
+    let mut columns = vec!{};
+    if expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+        cursor += 1;
+
+        let (identifiers, new_cursor, _) = parse_identifiers(tokens, cursor, vec!{&token_from_symbol(RIGHTPAREN_SYMBOL)})?;
+        columns = identifiers.unwrap();
+        cursor = new_cursor;
+
+        if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+            return Err(help_message(tokens, cursor, "Expected closing paren".to_string()));
+        }
+        cursor += 1;
+    }
+
+    if !expect_token(tokens, cursor, token_from_keyword(AS_KEYWORD)) {
+        return Err(help_message(tokens, cursor, "Expected AS".to_string()));
     }
     cursor += 1;
 
-    let (table, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    let (subquery, new_cursor, ok) = parse_subquery(tokens, cursor)?;
     if !ok {
-        help_message(tokens, cursor, "Expected table name".to_string());
-        return (None, initial_cursor, false)
+        return Err(help_message(tokens, cursor, "Expected subquery".to_string()));
     }
     cursor = new_cursor;
 
-    if !expect_token(tokens, cursor, token_from_keyword(VALUES_KEYWORD)){
-        help_message(tokens, cursor, "Expected VALUES".to_string());
-        return (None, initial_cursor, false);
+    Ok((Some(CommonTableExpression{
+        name: name.unwrap().clone(),
+        columns,
+        query: *subquery.unwrap().subquery.unwrap()
+    }), cursor, true))
+}
+
+/// Parses a SELECT or a compound query of SELECTs joined by UNION, INTERSECT
+/// and EXCEPT. ORDER BY, LIMIT and OFFSET after the last SELECT apply to
+/// the whole compound query.
+fn parse_select_statement(tokens: &[Token], initial_cursor: usize, delimiter: &Token) -> Result<(Option<SelectStatement>, usize, bool), Error> {
+    let (select, cursor, ok) = parse_set_operation(tokens, initial_cursor, delimiter, 0)?;
+    if !ok {
+        return Ok((None, initial_cursor, false));
     }
-    cursor += 1;
 
-    if !expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
-        help_message(tokens, cursor, "Expected left paren".to_string());
-        return (None, initial_cursor, false);
+    let mut select = select.unwrap();
+    if select.set_operation.is_some() {
+        let last = last_select(&mut select);
+        let order_by = std::mem::take(&mut last.order_by);
+        let limit = last.limit.take();
+        let offset = last.offset.take();
+
+        select.order_by = order_by;
+        select.limit = limit;
+        select.offset = offset;
     }
-    cursor += 1;
+    Ok((Some(select), cursor, true))
+}
+
+/// Parses SELECTs joined by set operations by precedence climbing, with
+/// INTERSECT binding tighter than UNION and EXCEPT.
+fn parse_set_operation(tokens: &[Token], initial_cursor: usize, delimiter: &Token, min_bp: u32) -> Result<(Option<SelectStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
 
-    let (values, new_cursor, ok) = parse_expressions(tokens, cursor, vec!{&token_from_symbol(RIGHTPAREN_SYMBOL)});
+    let (left, new_cursor, ok) = parse_select_core(tokens, cursor, delimiter)?;
     if !ok {
-        return (None, initial_cursor, false);
+        return Ok((None, initial_cursor, false));
     }
+    let mut select = left.unwrap();
     cursor = new_cursor;
 
-    if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
-        help_message(tokens, cursor, "Expected right paren".to_string());
-        return (None, initial_cursor, false);
+    loop {
+        let (op, bp) = if expect_token(tokens, cursor, token_from_keyword(UNION_KEYWORD)) {
+            (SetOperator::UnionOperator, 1)
+        } else if expect_token(tokens, cursor, token_from_keyword(EXCEPT_KEYWORD)) {
+            (SetOperator::ExceptOperator, 1)
+        } else if expect_token(tokens, cursor, token_from_keyword(INTERSECT_KEYWORD)) {
+            (SetOperator::IntersectOperator, 2)
+        } else {
+            break;
+        };
+        if bp < min_bp {
+            break;
+        }
+
+        let last = last_select(&mut select);
+        if !last.order_by.is_empty() || last.limit.is_some() || last.offset.is_some() {
+            return Err(help_message(tokens, cursor, "ORDER BY, LIMIT and OFFSET are only allowed after the last SELECT".to_string()));
+        }
+        cursor += 1;
+
+        let mut all = false;
+        if expect_token(tokens, cursor, token_from_keyword(ALL_KEYWORD)) {
+            cursor += 1;
+            all = true;
+        }
+
+        let (right, new_cursor, ok) = parse_set_operation(tokens, cursor, delimiter, bp + 1)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected SELECT".to_string()));
+        }
+        cursor = new_cursor;
+
+        select = SelectStatement{
+            distinct: false,
+            item: vec!{},
+            from: None,
+            filter: None,
+            group_by: vec!{},
+            having: None,
+            order_by: vec!{},
+            limit: None,
+            offset: None,
+            set_operation: Some(SetOperation{
+                left: Box::new(select),
+                right: Box::new(right.unwrap()),
+                op,
+                all
+            })
+        };
     }
-    cursor += 1;
+    Ok((Some(select), cursor, true))
+}
 
-    (Some(InsertStatement{
-        table: table.unwrap().clone(),
-        values: values.unwrap(),
-    }), cursor, true)
+/// The last SELECT of a possibly compound query.
+fn last_select(select: &mut SelectStatement) -> &mut SelectStatement {
+    match select.set_operation {
+        Some(ref mut set) => last_select(&mut set.right),
+        None => select
+    }
 }
 
-fn parse_create_table_statement(tokens: &Vec<Token>, initial_cursor: usize, _delimiter: &Token) -> (Option<CreateTableStatement>, usize, bool) {
+fn parse_select_core(tokens: &[Token], initial_cursor: usize, delimiter: &Token) -> Result<(Option<SelectStatement>, usize, bool), Error> {
     let mut cursor = initial_cursor;
 
-    if !expect_token(tokens, cursor, token_from_keyword(CREATE_KEYWORD)) {
-        return (None, initial_cursor, false);
+    if !expect_token(tokens, cursor, token_from_keyword(SELECT_KEYWORD)){
+        return Ok((None, initial_cursor, false));
     }
     cursor += 1;
 
-    if !expect_token(tokens, cursor, token_from_keyword(TABLE_KEYWORD)) {
-        return (None, initial_cursor, false);
+    let mut select = SelectStatement{
+        distinct: false,
+        item: vec!{},
+        from: None,
+        filter: None,
+        group_by: vec!{},
+        having: None,
+        order_by: vec!{},
+        limit: None,
+        offset: None,
+        set_operation: None
+    };
+
+    if expect_token(tokens, cursor, token_from_keyword(DISTINCT_KEYWORD)){
+        cursor += 1;
+        select.distinct = true;
     }
-    cursor += 1;
 
-    let (name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    let (items, new_cursor, ok) = parse_select_items(tokens, cursor, vec!{&token_from_keyword(FROM_KEYWORD), &token_from_keyword(WHERE_KEYWORD), &token_from_keyword(GROUP_KEYWORD), &token_from_keyword(HAVING_KEYWORD), &token_from_keyword(ORDER_KEYWORD), &token_from_keyword(LIMIT_KEYWORD), &token_from_keyword(OFFSET_KEYWORD), &token_from_keyword(UNION_KEYWORD), &token_from_keyword(INTERSECT_KEYWORD), &token_from_keyword(EXCEPT_KEYWORD), delimiter})?;
     if !ok {
-        help_message(tokens, cursor, "Expected table name".to_string());
-        return (None, initial_cursor, false);
+        return Ok((None, initial_cursor, false));
     }
+    select.item = items.unwrap();
     cursor = new_cursor;
 
-    if !expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
-        help_message(tokens, cursor, "Expected left parenthesis".to_string());
-        return (None, initial_cursor, false);
+    if expect_token(tokens, cursor, token_from_keyword(FROM_KEYWORD)){
+        cursor += 1;
+
+        let (from, new_cursor, _) = parse_from_item(tokens, cursor)?;
+        select.from = from;
+        cursor = new_cursor;
     }
-    cursor += 1;
 
-    let (cols, new_cursor, ok) = parse_column_definitions(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL));
-    if !ok {
-        return (None, initial_cursor, false);
+    if expect_token(tokens, cursor, token_from_keyword(WHERE_KEYWORD)){
+        cursor += 1;
+
+        let (filter, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected WHERE conditionals".to_string()));
+        }
+        select.filter = filter;
+        cursor = new_cursor;
     }
-    cursor = new_cursor;
 
-    if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
-        help_message(tokens, cursor, "Expected right parenthesis".to_string());
-        return (None, initial_cursor, false);
+    if expect_token(tokens, cursor, token_from_keyword(GROUP_KEYWORD)){
+        cursor += 1;
+
+        if !expect_token(tokens, cursor, token_from_keyword(BY_KEYWORD)){
+            return Err(help_message(tokens, cursor, "Expected BY".to_string()));
+        }
+        cursor += 1;
+
+        let (group_by, new_cursor, _) = parse_expressions(tokens, cursor, vec!{
+            &token_from_keyword(HAVING_KEYWORD),
+            &token_from_keyword(ORDER_KEYWORD),
+            &token_from_keyword(LIMIT_KEYWORD),
+            &token_from_keyword(OFFSET_KEYWORD),
+            &token_from_keyword(UNION_KEYWORD),
+            &token_from_keyword(INTERSECT_KEYWORD),
+            &token_from_keyword(EXCEPT_KEYWORD),
+            delimiter
+        })?;
+        select.group_by = group_by.unwrap();
+        cursor = new_cursor;
+
+        if select.group_by.is_empty() {
+            return Err(help_message(tokens, cursor, "Expected GROUP BY expression".to_string()));
+        }
     }
-    cursor += 1;
 
-    return (Some(CreateTableStatement{
-        name: name.unwrap().clone(),
-        cols: cols.unwrap(),
-    }), cursor, true)
+    if expect_token(tokens, cursor, token_from_keyword(HAVING_KEYWORD)){
+        cursor += 1;
+
+        let (having, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected HAVING conditionals".to_string()));
+        }
+        select.having = having;
+        cursor = new_cursor;
+    }
+
+    if expect_token(tokens, cursor, token_from_keyword(ORDER_KEYWORD)){
+        cursor += 1;
+
+        if !expect_token(tokens, cursor, token_from_keyword(BY_KEYWORD)){
+            return Err(help_message(tokens, cursor, "Expected BY".to_string()));
+        }
+        cursor += 1;
+
+        let (order_by, new_cursor, _) = parse_order_by_items(tokens, cursor)?;
+        select.order_by = order_by.unwrap();
+        cursor = new_cursor;
+    }
+
+    if expect_token(tokens, cursor, token_from_keyword(LIMIT_KEYWORD)){
+        cursor += 1;
+
+        let (limit, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected LIMIT expression".to_string()));
+        }
+        select.limit = limit;
+        cursor = new_cursor;
+    }
+
+    if expect_token(tokens, cursor, token_from_keyword(OFFSET_KEYWORD)){
+        cursor += 1;
+
+        let (offset, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected OFFSET expression".to_string()));
+        }
+        select.offset = offset;
+        cursor = new_cursor;
+    }
+
+    Ok((Some(select), cursor, true))
 }
 
-fn parse_column_definitions(tokens: &Vec<Token>, initial_cursor: usize, delimiter: Token) -> (Option<Vec<ColumnDefinition>>, usize, bool) {
+fn parse_order_by_items(tokens: &[Token], initial_cursor: usize) -> Result<(Option<Vec<OrderByItem>>, usize, bool), Error> {
     let mut cursor = initial_cursor;
 
-    let mut cds:Vec<ColumnDefinition> = vec!{};
-
+    let mut items: Vec<OrderByItem> = vec!{};
     loop {
-        if cursor >= tokens.len() {
-            return (None, initial_cursor, false);
+        if !items.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                break;
+            }
+            cursor += 1;
         }
 
-        let current = &tokens[cursor];
-        if delimiter == *current {
-            break
+        let (exp, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected ORDER BY expression".to_string()));
         }
+        cursor = new_cursor;
 
-        if cds.len() > 0 {
-            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
-                help_message(tokens, cursor, "Expected comma".to_string());
-                return (None, initial_cursor, false);
+        let mut desc = false;
+        if expect_token(tokens, cursor, token_from_keyword(DESC_KEYWORD)) {
+            cursor += 1;
+            desc = true;
+        } else if expect_token(tokens, cursor, token_from_keyword(ASC_KEYWORD)) {
+            cursor += 1;
+        }
+
+        // NULLS, FIRST and LAST are not reserved, so they lex as identifiers.
+        let mut nulls_first = desc;
+        if expect_token(tokens, cursor, token_from_identifier("nulls")) {
+            cursor += 1;
+            if expect_token(tokens, cursor, token_from_identifier("first")) {
+                nulls_first = true;
+            } else if expect_token(tokens, cursor, token_from_identifier("last")) {
+                nulls_first = false;
+            } else {
+                return Err(help_message(tokens, cursor, "Expected FIRST or LAST".to_string()));
+            }
+            cursor += 1;
+        }
+
+        items.push(OrderByItem{
+            exp: exp.unwrap(),
+            desc,
+            nulls_first
+        });
+    }
+    Ok((Some(items), cursor, true))
+}
+
+/// Parses a table reference followed by any number of joins, which nest to
+/// the left.
+fn parse_from_item(tokens: &[Token], initial_cursor: usize) -> Result<(Option<FromItem>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let (table, new_cursor, _) = parse_table_reference(tokens, cursor)?;
+    cursor = new_cursor;
+    let mut from = table.unwrap();
+
+    loop {
+        let comma = expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL));
+        let join_type;
+        if comma || expect_token(tokens, cursor, token_from_keyword(CROSS_KEYWORD)) {
+            cursor += 1;
+            join_type = JoinType::CrossJoin;
+        } else if expect_token(tokens, cursor, token_from_keyword(LEFT_KEYWORD)) {
+            cursor += 1;
+            if expect_token(tokens, cursor, token_from_keyword(OUTER_KEYWORD)) {
+                cursor += 1;
             }
+            join_type = JoinType::LeftJoin;
+        } else if expect_token(tokens, cursor, token_from_keyword(INNER_KEYWORD)) {
+            cursor += 1;
+            join_type = JoinType::InnerJoin;
+        } else if expect_token(tokens, cursor, token_from_keyword(JOIN_KEYWORD)) {
+            join_type = JoinType::InnerJoin;
+        } else {
+            break;
+        }
 
+        // Only the comma form has no JOIN keyword.
+        if !comma {
+            if !expect_token(tokens, cursor, token_from_keyword(JOIN_KEYWORD)) {
+                return Err(help_message(tokens, cursor, "Expected JOIN".to_string()));
+            }
             cursor += 1;
         }
 
-        let (id, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
-        if !ok {
-            help_message(tokens, cursor, "Expected column name".to_string());
-            return (None, initial_cursor, false);
+        let (right, new_cursor, _) = parse_table_reference(tokens, cursor)?;
+        cursor = new_cursor;
+
+        let mut on = None;
+        if join_type != JoinType::CrossJoin {
+            if !expect_token(tokens, cursor, token_from_keyword(ON_KEYWORD)) {
+                return Err(help_message(tokens, cursor, "Expected ON".to_string()));
+            }
+            cursor += 1;
+
+            let (exp, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+            if !ok {
+                return Err(help_message(tokens, cursor, "Expected join condition".to_string()));
+            }
+            cursor = new_cursor;
+            on = exp;
         }
+
+        from = FromItem{
+            table: None,
+            alias: None,
+            join: Some(JoinItem{
+                left: Box::new(from),
+                right: Box::new(right.unwrap()),
+                join_type,
+                on
+            }),
+            subquery: None,
+            kind: FromItemKind::JoinKind
+        };
+    }
+    Ok((Some(from), cursor, true))
+}
+
+fn parse_table_reference(tokens: &[Token], initial_cursor: usize) -> Result<(Option<FromItem>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let (subquery, new_cursor, is_subquery) = parse_subquery(tokens, cursor)?;
+    let mut table = None;
+    if is_subquery {
         cursor = new_cursor;
+    } else if let (name, new_cursor, true) = parse_token(tokens, cursor, TokenKind::IdentifierKind) {
+        cursor = new_cursor;
+        table = name.cloned();
+    } else {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
 
-        let (ty, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::KeywordKind);
+    let mut alias = None;
+    if expect_token(tokens, cursor, token_from_keyword(AS_KEYWORD)) {
+        cursor += 1;
+        let (name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
         if !ok {
-            help_message(tokens, cursor, "Expected column type".to_string());
-            return (None , initial_cursor, false);
+            return Err(help_message(tokens, cursor, "Expected table alias".to_string()));
         }
         cursor = new_cursor;
+        alias = name;
+    } else if let (name, new_cursor, true) = parse_token(tokens, cursor, TokenKind::IdentifierKind) {
+        cursor = new_cursor;
+        alias = name;
+    }
 
-        cds.push(ColumnDefinition{
-            name: id.unwrap().clone(),
-            datatype: ty.unwrap().clone(),
-        });
+    if is_subquery {
+        if alias.is_none() {
+            return Err(help_message(tokens, cursor, "Expected subquery alias".to_string()));
+        }
+
+        return Ok((Some(FromItem{
+            table: None,
+            alias: alias.cloned(),
+            join: None,
+            subquery: subquery.unwrap().subquery,
+            kind: FromItemKind::SubqueryKind
+        }), cursor, true));
     }
-    (Some(cds), cursor, true)
-}
\ No newline at end of file
+
+    Ok((Some(FromItem{
+        table,
+        alias: alias.cloned(),
+        join: None,
+        subquery: None,
+        kind: FromItemKind::TableKind
+    }), cursor, true))
+}
+
+fn parse_token(tokens: &[Token], initial_cursor: usize, kind: TokenKind) -> (Option<&Token>, usize, bool){
+    let cursor = initial_cursor;
+
+    if cursor >= tokens.len() {
+        return (None, initial_cursor, false);
+    }
+
+    let current = &tokens[cursor];
+    if current.kind == kind {
+        return (Some(current), cursor + 1, true);
+    }
+    (None, initial_cursor, false)
+}
+
+fn parse_select_items(tokens: &[Token], initial_cursor: usize, delimiters: Vec<&Token>) -> Result<(Option<Vec<SelectItem>>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let mut items: Vec<SelectItem> = vec!{};
+    'outer: loop{
+        if cursor >= tokens.len() {
+            break;
+        }
+
+        let current = &tokens[cursor];
+
+        for delimiter in &delimiters {
+            if *delimiter == current {
+                break 'outer;
+            }
+        }
+
+        if !items.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                return Err(help_message(tokens, cursor, "Expected comma".to_string()));
+            }
+            cursor += 1;
+        }
+
+        let mut item = SelectItem{
+            exp: None,
+            asterisk: false,
+            table: None,
+            as_name: None
+        };
+
+        if expect_token(tokens, cursor, token_from_symbol(ASTERISK_SYMBOL)) {
+            item.asterisk = true;
+            cursor += 1;
+        } else if cursor < tokens.len()
+            && tokens[cursor].kind == TokenKind::IdentifierKind
+            && expect_token(tokens, cursor + 1, token_from_symbol(DOT_SYMBOL))
+            && expect_token(tokens, cursor + 2, token_from_symbol(ASTERISK_SYMBOL)) {
+            item.asterisk = true;
+            item.table = Some(tokens[cursor].clone());
+            cursor += 3;
+        } else if let(expression, new_cursor, true) = parse_expression(tokens, cursor, 0)? {
+            item.exp = expression;
+            cursor = new_cursor;
+
+            if expect_token(tokens, cursor, token_from_keyword(AS_KEYWORD)) {
+                cursor += 1;
+
+                let (id, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+                if !ok {
+                    return Err(help_message(tokens, cursor, "Expected identifier after AS".to_string()));
+                }
+
+                item.as_name = id.cloned();
+                cursor = new_cursor;
+            }
+        } else{
+            return Err(help_message(tokens, cursor, "Expected expression".to_string()));
+        }
+
+        items.push(item);
+    }
+    Ok((Some(items), cursor, true))
+}
+
+fn parse_expressions(tokens: &[Token], initial_cursor: usize, delimiters: Vec<&Token>) -> Result<(Option<Vec<Expression>>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let mut expressions:Vec<Expression> = vec!{};
+    'outer: loop{
+        if cursor >= tokens.len() {
+            break;
+        }
+
+        let current = &tokens[cursor];
+
+        for delimiter in &delimiters {
+            if *delimiter == current {
+                break 'outer;
+            }
+        }
+
+        if !expressions.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                return Err(help_message(tokens, cursor, "Expected comma".to_string()));
+            }
+            cursor += 1;
+        }
+
+        if let(expression, new_cursor, true) = parse_expression(tokens, cursor, 0)? {
+            cursor = new_cursor;
+            expressions.push(expression.unwrap());
+        } else{
+            return Err(help_message(tokens, cursor, "Expected expression".to_string()));
+        }
+    }
+    Ok((Some(expressions), cursor, true))
+}
+
+fn parse_literal_expression(tokens: &[Token], initial_cursor: usize) -> Result<(Option<Expression>, usize, bool), Error> {
+    let cursor = initial_cursor;
+
+    let kinds = vec!{TokenKind::IdentifierKind, TokenKind::NumericKind, TokenKind::StringKind, TokenKind::BooleanKind, TokenKind::NullKind};
+
+    // A qualified column reference such as `users.id`.
+    if let(table, new_cursor, true) = parse_token(tokens, cursor, TokenKind::IdentifierKind) {
+        if expect_token(tokens, new_cursor, token_from_symbol(DOT_SYMBOL)) {
+            let (column, new_cursor, ok) = parse_token(tokens, new_cursor + 1, TokenKind::IdentifierKind);
+            if !ok {
+                return Err(help_message(tokens, cursor + 2, "Expected column name".to_string()));
+            }
+
+            return Ok((Some(Expression{
+                literal: Some(column.unwrap().clone()),
+                table: Some(table.unwrap().clone()),
+                binary: None,
+                unary: None,
+                function: None,
+                subquery: None,
+                kind: ExpressionKind::LiteralKind
+            }), new_cursor, true));
+        }
+    }
+
+    for kind in kinds {
+        if let(t, new_cursor, true) = parse_token(tokens, cursor, kind) {
+            return Ok((Some(Expression{
+                literal: Some(t.unwrap().clone()),
+                table: None,
+                binary: None,
+                unary: None,
+                function: None,
+                subquery: None,
+                kind: ExpressionKind::LiteralKind
+            }), new_cursor, true));
+        }
+    }
+    Ok((None, initial_cursor, false))
+}
+
+fn parse_function_expression(tokens: &[Token], initial_cursor: usize) -> Result<(Option<Expression>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let (name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok || !expect_token(tokens, new_cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor = new_cursor + 1;
+
+    let mut function = FunctionExpression{
+        name: name.unwrap().clone(),
+        distinct: false,
+        args: vec!{},
+        asterisk: false,
+        over: None
+    };
+
+    if expect_token(tokens, cursor, token_from_keyword(DISTINCT_KEYWORD)) {
+        cursor += 1;
+        function.distinct = true;
+    }
+
+    if !function.distinct && expect_token(tokens, cursor, token_from_symbol(ASTERISK_SYMBOL)) {
+        cursor += 1;
+        function.asterisk = true;
+    } else {
+        let (args, new_cursor, _) = parse_expressions(tokens, cursor, vec!{&token_from_symbol(RIGHTPAREN_SYMBOL)})?;
+        cursor = new_cursor;
+        function.args = args.unwrap();
+    }
+
+    if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+        return Err(help_message(tokens, cursor, "Expected closing paren".to_string()));
+    }
+    cursor += 1;
+
+    if expect_token(tokens, cursor, token_from_identifier("over")) {
+        let (over, new_cursor, _) = parse_window_specification(tokens, cursor + 1)?;
+        cursor = new_cursor;
+        function.over = over;
+    }
+
+    Ok((Some(Expression{
+        literal: None,
+        table: None,
+        binary: None,
+        unary: None,
+        function: Some(function),
+        subquery: None,
+        kind: ExpressionKind::FunctionKind
+    }), cursor, true))
+}
+
+/// Parses the parenthesized window after OVER. PARTITION, ROWS, RANGE and
+/// the words of frame bounds are not reserved, so they lex as identifiers.
+fn parse_window_specification(tokens: &[Token], initial_cursor: usize) -> Result<(Option<WindowSpecification>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+        return Err(help_message(tokens, cursor, "Expected window specification".to_string()));
+    }
+    cursor += 1;
+
+    let mut window = WindowSpecification{
+        partition_by: vec!{},
+        order_by: vec!{},
+        frame: None
+    };
+
+    if expect_token(tokens, cursor, token_from_identifier("partition")) {
+        cursor += 1;
+
+        if !expect_token(tokens, cursor, token_from_keyword(BY_KEYWORD)) {
+            return Err(help_message(tokens, cursor, "Expected BY".to_string()));
+        }
+        cursor += 1;
+
+        let (partition_by, new_cursor, _) = parse_expressions(tokens, cursor, vec!{
+            &token_from_keyword(ORDER_KEYWORD),
+            &token_from_identifier("rows"),
+            &token_from_identifier("range"),
+            &token_from_symbol(RIGHTPAREN_SYMBOL)
+        })?;
+        window.partition_by = partition_by.unwrap();
+        if window.partition_by.is_empty() {
+            return Err(help_message(tokens, cursor, "Expected PARTITION BY expression".to_string()));
+        }
+        cursor = new_cursor;
+    }
+
+    if expect_token(tokens, cursor, token_from_keyword(ORDER_KEYWORD)) {
+        cursor += 1;
+
+        if !expect_token(tokens, cursor, token_from_keyword(BY_KEYWORD)) {
+            return Err(help_message(tokens, cursor, "Expected BY".to_string()));
+        }
+        cursor += 1;
+
+        let (order_by, new_cursor, _) = parse_order_by_items(tokens, cursor)?;
+        window.order_by = order_by.unwrap();
+        cursor = new_cursor;
+    }
+
+    let units = if expect_token(tokens, cursor, token_from_identifier("rows")) {
+        Some(FrameUnits::RowsUnits)
+    } else if expect_token(tokens, cursor, token_from_identifier("range")) {
+        Some(FrameUnits::RangeUnits)
+    } else {
+        None
+    };
+    if let Some(units) = units {
+        cursor += 1;
+
+        let between = expect_token(tokens, cursor, token_from_identifier("between"));
+        if between {
+            cursor += 1;
+        }
+
+        let start_cursor = cursor;
+        let (start, new_cursor, _) = parse_frame_bound(tokens, cursor)?;
+        let start = start.unwrap();
+        if start.kind == FrameBoundKind::UnboundedFollowingKind {
+            return Err(help_message(tokens, start_cursor, "Frame start cannot be UNBOUNDED FOLLOWING".to_string()));
+        }
+        cursor = new_cursor;
+
+        let mut end = FrameBound{
+            offset: None,
+            kind: FrameBoundKind::CurrentRowKind
+        };
+        if between {
+            if !expect_token(tokens, cursor, token_from_keyword(AND_KEYWORD)) {
+                return Err(help_message(tokens, cursor, "Expected AND".to_string()));
+            }
+            cursor += 1;
+
+            let end_cursor = cursor;
+            let (bound, new_cursor, _) = parse_frame_bound(tokens, cursor)?;
+            end = bound.unwrap();
+            if end.kind == FrameBoundKind::UnboundedPrecedingKind {
+                return Err(help_message(tokens, end_cursor, "Frame end cannot be UNBOUNDED PRECEDING".to_string()));
+            }
+            cursor = new_cursor;
+        }
+
+        window.frame = Some(WindowFrame{
+            units,
+            start,
+            end
+        });
+    }
+
+    if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+        return Err(help_message(tokens, cursor, "Expected closing paren".to_string()));
+    }
+    cursor += 1;
+
+    Ok((Some(window), cursor, true))
+}
+
+/// Parses `UNBOUNDED PRECEDING`, `n PRECEDING`, `CURRENT ROW`,
+/// `n FOLLOWING` or `UNBOUNDED FOLLOWING`.
+fn parse_frame_bound(tokens: &[Token], initial_cursor: usize) -> Result<(Option<FrameBound>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if expect_token(tokens, cursor, token_from_identifier("current")) {
+        cursor += 1;
+
+        if !expect_token(tokens, cursor, token_from_identifier("row")) {
+            return Err(help_message(tokens, cursor, "Expected ROW".to_string()));
+        }
+        cursor += 1;
+
+        return Ok((Some(FrameBound{
+            offset: None,
+            kind: FrameBoundKind::CurrentRowKind
+        }), cursor, true));
+    }
+
+    let mut offset = None;
+    if expect_token(tokens, cursor, token_from_identifier("unbounded")) {
+        cursor += 1;
+    } else {
+        let (exp, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected frame bound".to_string()));
+        }
+        offset = exp.map(Box::new);
+        cursor = new_cursor;
+    }
+
+    let kind = match (expect_token(tokens, cursor, token_from_identifier("preceding")), offset.is_some()) {
+        (true, true) => FrameBoundKind::PrecedingKind,
+        (true, false) => FrameBoundKind::UnboundedPrecedingKind,
+        _ if !expect_token(tokens, cursor, token_from_identifier("following")) => {
+            return Err(help_message(tokens, cursor, "Expected PRECEDING or FOLLOWING".to_string()));
+        },
+        (false, true) => FrameBoundKind::FollowingKind,
+        (false, false) => FrameBoundKind::UnboundedFollowingKind
+    };
+    cursor += 1;
+
+    Ok((Some(FrameBound{
+        offset,
+        kind
+    }), cursor, true))
+}
+
+/// Binding power of a binary operator token, 0 if the token is not one.
+fn binding_power(token: &Token) -> u32 {
+    match token.kind {
+        TokenKind::KeywordKind => match &token.value[..] {
+            OR_KEYWORD => 1,
+            AND_KEYWORD => 2,
+            IS_KEYWORD => 4,
+            // NOT only follows an operand in `NOT IN`.
+            IN_KEYWORD | NOT_KEYWORD => 5,
+            _ => 0,
+        },
+        TokenKind::SymbolKind => match &token.value[..] {
+            EQ_SYMBOL | NEQ_SYMBOL | LT_SYMBOL | LTE_SYMBOL | GT_SYMBOL | GTE_SYMBOL => 5,
+            CONCAT_SYMBOL | PLUS_SYMBOL | MINUS_SYMBOL => 6,
+            ASTERISK_SYMBOL | SLASH_SYMBOL => 7,
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// Binding power of a prefix operator token, 0 if the token is not one.
+fn prefix_binding_power(token: &Token) -> u32 {
+    match token.kind {
+        TokenKind::KeywordKind if token.value == NOT_KEYWORD => 3,
+        TokenKind::SymbolKind if token.value == MINUS_SYMBOL => 8,
+        _ => 0,
+    }
+}
+
+/// Parses an expression by precedence climbing, only consuming binary
+/// operators whose binding power is at least `min_bp`.
+fn parse_expression(tokens: &[Token], initial_cursor: usize, min_bp: u32) -> Result<(Option<Expression>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if cursor >= tokens.len() {
+        return Ok((None, initial_cursor, false));
+    }
+
+    let mut exp;
+    let prefix_bp = prefix_binding_power(&tokens[cursor]);
+    if let (Some(subquery), new_cursor, true) = parse_subquery(tokens, cursor)? {
+        exp = subquery;
+        cursor = new_cursor;
+    } else if expect_token(tokens, cursor, token_from_keyword(EXISTS_KEYWORD)) {
+        let (subquery, new_cursor, ok) = parse_subquery(tokens, cursor + 1)?;
+        if !ok {
+            return Err(help_message(tokens, cursor + 1, "Expected subquery".to_string()));
+        }
+
+        exp = Expression{
+            literal: None,
+            table: None,
+            binary: None,
+            unary: Some(UnaryExpression{
+                operand: Box::new(subquery.unwrap()),
+                op: tokens[cursor].clone()
+            }),
+            function: None,
+            subquery: None,
+            kind: ExpressionKind::UnaryKind
+        };
+        cursor = new_cursor;
+    } else if expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+        cursor += 1;
+
+        let (inner, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected expression after opening paren".to_string()));
+        }
+        cursor = new_cursor;
+
+        if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+            return Err(help_message(tokens, cursor, "Expected closing paren".to_string()));
+        }
+        cursor += 1;
+
+        exp = inner.unwrap();
+    } else if prefix_bp > 0 {
+        let (operand, new_cursor, ok) = parse_expression(tokens, cursor + 1, prefix_bp)?;
+        if !ok {
+            return Err(help_message(tokens, cursor + 1, "Expected operand".to_string()));
+        }
+
+        exp = Expression{
+            literal: None,
+            table: None,
+            binary: None,
+            unary: Some(UnaryExpression{
+                operand: Box::new(operand.unwrap()),
+                op: tokens[cursor].clone()
+            }),
+            function: None,
+            subquery: None,
+            kind: ExpressionKind::UnaryKind
+        };
+        cursor = new_cursor;
+    } else if let (Some(function), new_cursor, true) = parse_function_expression(tokens, cursor)? {
+        exp = function;
+        cursor = new_cursor;
+    } else {
+        let (literal, new_cursor, ok) = parse_literal_expression(tokens, cursor)?;
+        if !ok {
+            return Ok((None, initial_cursor, false));
+        }
+
+        exp = literal.unwrap();
+        cursor = new_cursor;
+    }
+
+    while cursor < tokens.len() {
+        let op = &tokens[cursor];
+        let bp = binding_power(op);
+        if bp == 0 || bp < min_bp {
+            break;
+        }
+
+        if expect_token(tokens, cursor, token_from_keyword(IS_KEYWORD)) {
+            let (is_null, new_cursor, _) = parse_is_null(tokens, cursor, exp)?;
+            exp = is_null.unwrap();
+            cursor = new_cursor;
+            continue;
+        }
+
+        if expect_token(tokens, cursor, token_from_keyword(IN_KEYWORD)) || expect_token(tokens, cursor, token_from_keyword(NOT_KEYWORD)) {
+            let (is_in, new_cursor, _) = parse_in(tokens, cursor, exp)?;
+            exp = is_in.unwrap();
+            cursor = new_cursor;
+            continue;
+        }
+
+        let (b, new_cursor, ok) = parse_expression(tokens, cursor + 1, bp + 1)?;
+        if !ok {
+            return Err(help_message(tokens, cursor + 1, "Expected right operand".to_string()));
+        }
+
+        exp = Expression{
+            literal: None,
+            table: None,
+            binary: Some(BinaryExpression{
+                a: Box::new(exp),
+                b: Box::new(b.unwrap()),
+                op: op.clone()
+            }),
+            unary: None,
+            function: None,
+            subquery: None,
+            kind: ExpressionKind::BinaryKind
+        };
+        cursor = new_cursor;
+    }
+    Ok((Some(exp), cursor, true))
+}
+
+/// Parses the `IS [NOT] NULL` suffix applied to `operand`. `x IS NOT NULL`
+/// is represented as `NOT (x IS NULL)`.
+fn parse_is_null(tokens: &[Token], initial_cursor: usize, operand: Expression) -> Result<(Option<Expression>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let is = tokens[cursor].clone();
+    cursor += 1;
+
+    let mut not = None;
+    if expect_token(tokens, cursor, token_from_keyword(NOT_KEYWORD)) {
+        not = Some(tokens[cursor].clone());
+        cursor += 1;
+    }
+
+    let (null, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::NullKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected NULL".to_string()));
+    }
+    cursor = new_cursor;
+
+    let mut exp = Expression{
+        literal: None,
+        table: None,
+        binary: Some(BinaryExpression{
+            a: Box::new(operand),
+            b: Box::new(Expression{
+                literal: Some(null.unwrap().clone()),
+                table: None,
+                binary: None,
+                unary: None,
+                function: None,
+                subquery: None,
+                kind: ExpressionKind::LiteralKind
+            }),
+            op: is
+        }),
+        unary: None,
+        function: None,
+        subquery: None,
+        kind: ExpressionKind::BinaryKind
+    };
+
+    if let Some(op) = not {
+        exp = Expression{
+            literal: None,
+            table: None,
+            binary: None,
+            unary: Some(UnaryExpression{
+                operand: Box::new(exp),
+                op
+            }),
+            function: None,
+            subquery: None,
+            kind: ExpressionKind::UnaryKind
+        };
+    }
+    Ok((Some(exp), cursor, true))
+}
+
+/// Parses the `[NOT] IN (SELECT ...)` suffix applied to `operand`.
+/// `x NOT IN (...)` is represented as `NOT (x IN (...))`.
+fn parse_in(tokens: &[Token], initial_cursor: usize, operand: Expression) -> Result<(Option<Expression>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let mut not = None;
+    if expect_token(tokens, cursor, token_from_keyword(NOT_KEYWORD)) {
+        not = Some(tokens[cursor].clone());
+        cursor += 1;
+    }
+
+    if !expect_token(tokens, cursor, token_from_keyword(IN_KEYWORD)) {
+        return Err(help_message(tokens, cursor, "Expected IN".to_string()));
+    }
+    let op = tokens[cursor].clone();
+    cursor += 1;
+
+    let (subquery, new_cursor, ok) = parse_subquery(tokens, cursor)?;
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected subquery".to_string()));
+    }
+    cursor = new_cursor;
+
+    let mut exp = Expression{
+        literal: None,
+        table: None,
+        binary: Some(BinaryExpression{
+            a: Box::new(operand),
+            b: Box::new(subquery.unwrap()),
+            op
+        }),
+        unary: None,
+        function: None,
+        subquery: None,
+        kind: ExpressionKind::BinaryKind
+    };
+
+    if let Some(op) = not {
+        exp = Expression{
+            literal: None,
+            table: None,
+            binary: None,
+            unary: Some(UnaryExpression{
+                operand: Box::new(exp),
+                op
+            }),
+            function: None,
+            subquery: None,
+            kind: ExpressionKind::UnaryKind
+        };
+    }
+    Ok((Some(exp), cursor, true))
+}
+
+/// Parses a parenthesized SELECT.
+fn parse_subquery(tokens: &[Token], initial_cursor: usize) -> Result<(Option<Expression>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL))
+        || !expect_token(tokens, cursor + 1, token_from_keyword(SELECT_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    let (select, new_cursor, ok) = parse_select_statement(tokens, cursor, &token_from_symbol(RIGHTPAREN_SYMBOL))?;
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected SELECT".to_string()));
+    }
+    cursor = new_cursor;
+
+    if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+        return Err(help_message(tokens, cursor, "Expected closing paren".to_string()));
+    }
+    cursor += 1;
+
+    Ok((Some(Expression{
+        literal: None,
+        table: None,
+        binary: None,
+        unary: None,
+        function: None,
+        subquery: Some(Box::new(select.unwrap())),
+        kind: ExpressionKind::SubqueryKind
+    }), cursor, true))
+}
+
+fn parse_insert_statement(tokens: &[Token], initial_cursor: usize, _delimiter: &Token) -> Result<(Option<InsertStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(INSERT_KEYWORD)){
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    if !expect_token(tokens, cursor, token_from_keyword(INTO_KEYWORD)){
+        return Err(help_message(tokens, cursor, "Expected into".to_string()));
+    }
+    cursor += 1;
+
+    let (table, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
+    cursor = new_cursor;
+
+    let mut columns = None;
+    if expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+        cursor += 1;
+
+        let (names, new_cursor, _) = parse_identifiers(tokens, cursor, vec!{&token_from_symbol(RIGHTPAREN_SYMBOL)})?;
+        cursor = new_cursor;
+
+        if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+            return Err(help_message(tokens, cursor, "Expected right paren".to_string()));
+        }
+        cursor += 1;
+        columns = names;
+    }
+
+    if !expect_token(tokens, cursor, token_from_keyword(VALUES_KEYWORD)){
+        return Err(help_message(tokens, cursor, "Expected VALUES".to_string()));
+    }
+    cursor += 1;
+
+    let mut rows: Vec<Vec<Expression>> = vec!{};
+    loop {
+        if !rows.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                break;
+            }
+            cursor += 1;
+        }
+
+        if !expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+            return Err(help_message(tokens, cursor, "Expected left paren".to_string()));
+        }
+        cursor += 1;
+
+        let (values, new_cursor, ok) = parse_expressions(tokens, cursor, vec!{&token_from_symbol(RIGHTPAREN_SYMBOL)})?;
+        if !ok {
+            return Ok((None, initial_cursor, false));
+        }
+        cursor = new_cursor;
+
+        if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+            return Err(help_message(tokens, cursor, "Expected right paren".to_string()));
+        }
+        cursor += 1;
+
+        rows.push(values.unwrap());
+    }
+
+    Ok((Some(InsertStatement{
+        table: table.unwrap().clone(),
+        columns,
+        values: rows,
+    }), cursor, true))
+}
+
+fn parse_identifiers(tokens: &[Token], initial_cursor: usize, delimiters: Vec<&Token>) -> Result<(Option<Vec<Token>>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let mut identifiers: Vec<Token> = vec!{};
+    'outer: loop {
+        if cursor >= tokens.len() {
+            break;
+        }
+
+        let current = &tokens[cursor];
+
+        for delimiter in &delimiters {
+            if *delimiter == current {
+                break 'outer;
+            }
+        }
+
+        if !identifiers.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                return Err(help_message(tokens, cursor, "Expected comma".to_string()));
+            }
+            cursor += 1;
+        }
+
+        let (id, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected column name".to_string()));
+        }
+        cursor = new_cursor;
+        identifiers.push(id.unwrap().clone());
+    }
+    Ok((Some(identifiers), cursor, true))
+}
+
+fn parse_update_statement(tokens: &[Token], initial_cursor: usize, _delimiter: &Token) -> Result<(Option<UpdateStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(UPDATE_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    let (table, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
+    cursor = new_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(SET_KEYWORD)) {
+        return Err(help_message(tokens, cursor, "Expected SET".to_string()));
+    }
+    cursor += 1;
+
+    let mut set: Vec<Assignment> = vec!{};
+    loop {
+        if !set.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                break;
+            }
+            cursor += 1;
+        }
+
+        let (column, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected column name".to_string()));
+        }
+        cursor = new_cursor;
+
+        if !expect_token(tokens, cursor, token_from_symbol(EQ_SYMBOL)) {
+            return Err(help_message(tokens, cursor, "Expected =".to_string()));
+        }
+        cursor += 1;
+
+        let (value, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected expression".to_string()));
+        }
+        cursor = new_cursor;
+
+        set.push(Assignment{
+            column: column.unwrap().clone(),
+            value: value.unwrap()
+        });
+    }
+
+    let mut filter = None;
+    if expect_token(tokens, cursor, token_from_keyword(WHERE_KEYWORD)) {
+        cursor += 1;
+
+        let (exp, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected WHERE conditionals".to_string()));
+        }
+        filter = exp;
+        cursor = new_cursor;
+    }
+
+    Ok((Some(UpdateStatement{
+        table: table.unwrap().clone(),
+        set,
+        filter,
+    }), cursor, true))
+}
+
+fn parse_delete_statement(tokens: &[Token], initial_cursor: usize, _delimiter: &Token) -> Result<(Option<DeleteStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(DELETE_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    if !expect_token(tokens, cursor, token_from_keyword(FROM_KEYWORD)) {
+        return Err(help_message(tokens, cursor, "Expected FROM".to_string()));
+    }
+    cursor += 1;
+
+    let (table, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
+    cursor = new_cursor;
+
+    let mut filter = None;
+    if expect_token(tokens, cursor, token_from_keyword(WHERE_KEYWORD)) {
+        cursor += 1;
+
+        let (exp, new_cursor, ok) = parse_expression(tokens, cursor, 0)?;
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected WHERE conditionals".to_string()));
+        }
+        filter = exp;
+        cursor = new_cursor;
+    }
+
+    Ok((Some(DeleteStatement{
+        table: table.unwrap().clone(),
+        filter,
+    }), cursor, true))
+}
+
+fn parse_create_table_statement(tokens: &[Token], initial_cursor: usize, _delimiter: &Token) -> Result<(Option<CreateTableStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(CREATE_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    if !expect_token(tokens, cursor, token_from_keyword(TABLE_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    let mut if_not_exists = false;
+    if expect_token(tokens, cursor, token_from_keyword(IF_KEYWORD)) {
+        cursor += 1;
+        if !expect_token(tokens, cursor, token_from_keyword(NOT_KEYWORD)) {
+            return Err(help_message(tokens, cursor, "Expected NOT".to_string()));
+        }
+        cursor += 1;
+        if !expect_token(tokens, cursor, token_from_keyword(EXISTS_KEYWORD)) {
+            return Err(help_message(tokens, cursor, "Expected EXISTS".to_string()));
+        }
+        cursor += 1;
+        if_not_exists = true;
+    }
+
+    let (name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
+    cursor = new_cursor;
+
+    if !expect_token(tokens, cursor, token_from_symbol(LEFTPAREN_SYMBOL)) {
+        return Err(help_message(tokens, cursor, "Expected left parenthesis".to_string()));
+    }
+    cursor += 1;
+
+    let (cols, new_cursor, ok) = parse_column_definitions(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL))?;
+    if !ok {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor = new_cursor;
+
+    if !expect_token(tokens, cursor, token_from_symbol(RIGHTPAREN_SYMBOL)) {
+        return Err(help_message(tokens, cursor, "Expected right parenthesis".to_string()));
+    }
+    cursor += 1;
+
+    Ok((Some(CreateTableStatement{
+        name: name.unwrap().clone(),
+        cols: cols.unwrap(),
+        if_not_exists,
+    }), cursor, true))
+}
+
+fn parse_drop_table_statement(tokens: &[Token], initial_cursor: usize, _delimiter: &Token) -> Result<(Option<DropTableStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(DROP_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    if !expect_token(tokens, cursor, token_from_keyword(TABLE_KEYWORD)) {
+        return Err(help_message(tokens, cursor, "Expected TABLE".to_string()));
+    }
+    cursor += 1;
+
+    let mut if_exists = false;
+    if expect_token(tokens, cursor, token_from_keyword(IF_KEYWORD)) {
+        cursor += 1;
+        if !expect_token(tokens, cursor, token_from_keyword(EXISTS_KEYWORD)) {
+            return Err(help_message(tokens, cursor, "Expected EXISTS".to_string()));
+        }
+        cursor += 1;
+        if_exists = true;
+    }
+
+    let (name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
+    cursor = new_cursor;
+
+    Ok((Some(DropTableStatement{
+        name: name.unwrap().clone(),
+        if_exists,
+    }), cursor, true))
+}
+
+fn parse_column_definitions(tokens: &[Token], initial_cursor: usize, delimiter: Token) -> Result<(Option<Vec<ColumnDefinition>>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let mut cds:Vec<ColumnDefinition> = vec!{};
+
+    loop {
+        if cursor >= tokens.len() {
+            break;
+        }
+
+        let current = &tokens[cursor];
+        if delimiter == *current {
+            break
+        }
+
+        if !cds.is_empty() {
+            if !expect_token(tokens, cursor, token_from_symbol(COMMA_SYMBOL)) {
+                return Err(help_message(tokens, cursor, "Expected comma".to_string()));
+            }
+
+            cursor += 1;
+        }
+
+        let (cd, new_cursor, _) = parse_column_definition(tokens, cursor)?;
+        cursor = new_cursor;
+        cds.push(cd.unwrap());
+    }
+    Ok((Some(cds), cursor, true))
+}
+
+fn parse_column_definition(tokens: &[Token], initial_cursor: usize) -> Result<(Option<ColumnDefinition>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    let (id, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected column name".to_string()));
+    }
+    cursor = new_cursor;
+
+    let (ty, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::KeywordKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected column type".to_string()));
+    }
+    cursor = new_cursor;
+
+    let mut not_null = false;
+    if expect_token(tokens, cursor, token_from_keyword(NOT_KEYWORD)) {
+        cursor += 1;
+        let (_, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::NullKind);
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected NULL".to_string()));
+        }
+        cursor = new_cursor;
+        not_null = true;
+    } else if let (_, new_cursor, true) = parse_token(tokens, cursor, TokenKind::NullKind) {
+        cursor = new_cursor;
+    }
+
+    Ok((Some(ColumnDefinition{
+        name: id.unwrap().clone(),
+        datatype: ty.unwrap().clone(),
+        not_null,
+    }), cursor, true))
+}
+
+fn parse_alter_table_statement(tokens: &[Token], initial_cursor: usize, _delimiter: &Token) -> Result<(Option<AlterTableStatement>, usize, bool), Error> {
+    let mut cursor = initial_cursor;
+
+    if !expect_token(tokens, cursor, token_from_keyword(ALTER_KEYWORD)) {
+        return Ok((None, initial_cursor, false));
+    }
+    cursor += 1;
+
+    if !expect_token(tokens, cursor, token_from_keyword(TABLE_KEYWORD)) {
+        return Err(help_message(tokens, cursor, "Expected TABLE".to_string()));
+    }
+    cursor += 1;
+
+    let (table, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+    if !ok {
+        return Err(help_message(tokens, cursor, "Expected table name".to_string()));
+    }
+    cursor = new_cursor;
+
+    let mut alter = AlterTableStatement{
+        table: table.unwrap().clone(),
+        column_definition: None,
+        column: None,
+        new_name: None,
+        kind: AlterTableKind::AddColumnKind
+    };
+
+    if expect_token(tokens, cursor, token_from_keyword(ADD_KEYWORD)) {
+        cursor += 1;
+        if expect_token(tokens, cursor, token_from_keyword(COLUMN_KEYWORD)) {
+            cursor += 1;
+        }
+
+        let (cd, new_cursor, _) = parse_column_definition(tokens, cursor)?;
+        cursor = new_cursor;
+        alter.column_definition = cd;
+    } else if expect_token(tokens, cursor, token_from_keyword(DROP_KEYWORD)) {
+        cursor += 1;
+        if expect_token(tokens, cursor, token_from_keyword(COLUMN_KEYWORD)) {
+            cursor += 1;
+        }
+
+        let (column, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected column name".to_string()));
+        }
+        cursor = new_cursor;
+        alter.column = Some(column.unwrap().clone());
+        alter.kind = AlterTableKind::DropColumnKind;
+    } else if expect_token(tokens, cursor, token_from_keyword(RENAME_KEYWORD)) {
+        cursor += 1;
+
+        alter.kind = AlterTableKind::RenameTableKind;
+        let column_keyword = expect_token(tokens, cursor, token_from_keyword(COLUMN_KEYWORD));
+        if column_keyword {
+            cursor += 1;
+        }
+        if column_keyword || !expect_token(tokens, cursor, token_from_keyword(TO_KEYWORD)) {
+            let (column, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+            if !ok {
+                return Err(help_message(tokens, cursor, "Expected column name".to_string()));
+            }
+            cursor = new_cursor;
+            alter.column = Some(column.unwrap().clone());
+            alter.kind = AlterTableKind::RenameColumnKind;
+        }
+
+        if !expect_token(tokens, cursor, token_from_keyword(TO_KEYWORD)) {
+            return Err(help_message(tokens, cursor, "Expected TO".to_string()));
+        }
+        cursor += 1;
+
+        let (new_name, new_cursor, ok) = parse_token(tokens, cursor, TokenKind::IdentifierKind);
+        if !ok {
+            return Err(help_message(tokens, cursor, "Expected new name".to_string()));
+        }
+        cursor = new_cursor;
+        alter.new_name = Some(new_name.unwrap().clone());
+    } else {
+        return Err(help_message(tokens, cursor, "Expected ADD, DROP or RENAME".to_string()));
+    }
+
+    Ok((Some(alter), cursor, true))
+}
@@ -52,7 +52,12 @@ fn string(case: (bool, &str)) {
 
 #[parameterized(case= {
 	(true, "="),
-	(true, "||")
+	(true, "||"),
+	(true, "<="),
+	(true, "<>"),
+	(true, "-"),
+	(true, "."),
+	(false, ".5")
 })]
 fn symbol(case: (bool, &str)) {
     let (is_symbol, mut value) = case;
@@ -96,7 +101,10 @@ fn identifier(case: (bool, &str, &str)) {
 	(true,"as"),
 	(true,"SELECT"),
 	(true,"into"),
+	(true,"NULL"),
+	(false,"nullable"),
 	(false," into"),
+	(false,"orders"),
 	(false,"flubbrety")
 })]
 fn keyword(case: (bool, &str)) {
@@ -175,3 +183,16 @@ fn lex(case: (&str, Vec<Token>, Option<String>)) {
     assert_eq!(tokens.len(), input_tokens.len());
     assert_eq!(tokens, input_tokens);
 }
+
+#[parameterized(case = {
+	("105", 0),
+	("SELECT 105", 7),
+	("SELECT 1.5e2, 2", 7),
+	("SELECT 'a', 42", 12)
+})]
+fn numeric_location(case: (&str, usize)) {
+    let (source, col) = case;
+    let tokens = lex(source).unwrap();
+    let token = tokens.iter().find(|t| t.kind == TokenKind::NumericKind).unwrap();
+    assert_eq!(token.loc, Location{col, line: 0});
+}
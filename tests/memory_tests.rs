@@ -0,0 +1,568 @@
+use parameterized::parameterized;
+use rustsql::parser::*;
+use rustsql::ast::*;
+use rustsql::backend::*;
+use rustsql::memory::*;
+use rustsql::error::Error;
+
+fn execute(backend: &mut MemoryBackend, source: &str) -> Result<Option<Results>, Error> {
+	let mut results = None;
+	for stmt in parse(source)?.statements {
+		match stmt.kind {
+			AstKind::CreateTableKind => {
+				backend.create_table(&stmt.create_table_statement.unwrap())?;
+			},
+			AstKind::AlterTableKind => {
+				backend.alter_table(&stmt.alter_table_statement.unwrap())?;
+			},
+			AstKind::DropTableKind => {
+				backend.drop_table(&stmt.drop_table_statement.unwrap())?;
+			},
+			AstKind::InsertKind => {
+				backend.insert(&stmt.insert_statement.unwrap())?;
+			},
+			AstKind::UpdateKind => {
+				backend.update(&stmt.update_statement.unwrap())?;
+			},
+			AstKind::DeleteKind => {
+				backend.delete(&stmt.delete_statement.unwrap())?;
+			},
+			AstKind::SelectKind => {
+				results = Some(backend.select(&stmt.select_statement.unwrap())?);
+			}
+		}
+	}
+	Ok(results)
+}
+
+fn render(results: &Results) -> Vec<Vec<String>> {
+	results.rows.iter().map(|row| {
+		row.iter().enumerate().map(|(i, cell)| match results.columns[i].col_type {
+			_ if cell.is_null() => "NULL".to_string(),
+			ColumnType::IntType => cell.as_int().unwrap().to_string(),
+			ColumnType::TextType => cell.as_text().unwrap(),
+			ColumnType::BoolType => cell.as_bool().unwrap().to_string(),
+			ColumnType::NullType => "NULL".to_string()
+		}).collect()
+	}).collect()
+}
+
+fn setup() -> MemoryBackend {
+	let mut backend = MemoryBackend::new();
+	execute(&mut backend, "CREATE TABLE users (id INT, name TEXT);
+		INSERT INTO users VALUES (1, 'alice');
+		INSERT INTO users VALUES (2, 'bob');
+		INSERT INTO users VALUES (3, 'carol');").unwrap();
+	backend
+}
+
+fn setup_orders() -> MemoryBackend {
+	let mut backend = setup();
+	execute(&mut backend, "CREATE TABLE orders (user_id INT, total INT);
+		INSERT INTO orders VALUES (1, 10), (2, 7), (1, 5), (NULL, 3);").unwrap();
+	backend
+}
+
+#[parameterized(case = {
+	("SELECT id, name FROM users;", vec!{vec!{"1", "alice"}, vec!{"2", "bob"}, vec!{"3", "carol"}}),
+	("SELECT name FROM users WHERE id = 2;", vec!{vec!{"bob"}}),
+	("SELECT id FROM users WHERE id = 1 OR name = 'carol';", vec!{vec!{"1"}, vec!{"3"}}),
+	("SELECT id FROM users WHERE id <> 1 AND name <> 'carol';", vec!{vec!{"2"}}),
+	("SELECT id FROM users WHERE name = 'dave';", vec!{}),
+	("SELECT id * 2 + 1, name || '!' FROM users WHERE id < 3;", vec!{vec!{"3", "alice!"}, vec!{"5", "bob!"}}),
+	("SELECT (1 + 2) * 3 - 10 / 2, -id FROM users WHERE id >= 3;", vec!{vec!{"4", "-3"}}),
+	("SELECT name || ' #' || id FROM users WHERE NOT (id = 1 OR id > 2);", vec!{vec!{"bob #2"}}),
+	("SELECT id FROM users WHERE name <= 'bob' AND NOT id = 2;", vec!{vec!{"1"}}),
+	("SELECT * FROM users WHERE id = 1;", vec!{vec!{"1", "alice"}}),
+	("SELECT users.*, id FROM users WHERE id = 2;", vec!{vec!{"2", "bob", "2"}}),
+	("SELECT id = 2, id > 1 AND name <> 'carol' FROM users;", vec!{vec!{"false", "false"}, vec!{"true", "true"}, vec!{"false", "false"}}),
+	("SELECT id FROM users WHERE true AND NOT false;", vec!{vec!{"1"}, vec!{"2"}, vec!{"3"}}),
+	("SELECT 1, 'hello';", vec!{vec!{"1", "hello"}}),
+	("SELECT 1 + 2 * 3 AS n, 'a' || 'b', 2 > 1;", vec!{vec!{"7", "ab", "true"}}),
+	("SELECT 1 WHERE false;", vec!{}),
+	("SELECT -2147483648, -(2);", vec!{vec!{"-2147483648", "-2"}}),
+})]
+fn select(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[parameterized(case = {
+	("SELECT id FROM users WHERE id = 'bob';", Error::TypeMismatch("int = text".to_string())),
+	("SELECT id FROM users WHERE missing = 1;", Error::ColumnDoesNotExist("missing".to_string())),
+	("SELECT id FROM users WHERE id;", Error::TypeMismatch("condition must be boolean, got int".to_string())),
+	("SELECT id / 0 FROM users;", Error::DivisionByZero),
+	("SELECT id + name FROM users;", Error::TypeMismatch("int + text".to_string())),
+	("SELECT orders.* FROM users;", Error::TableDoesNotExist("orders".to_string())),
+	("SELECT id FROM orders;", Error::TableDoesNotExist("orders".to_string())),
+	("SELECT id;", Error::ColumnDoesNotExist("id".to_string())),
+	("INSERT INTO users VALUES (4);", Error::ArityMismatch{expected: 2, got: 1}),
+	("INSERT INTO users VALUES ('abc', 'x');", Error::TypeMismatch("column \"id\" is int, got text value abc".to_string())),
+	("INSERT INTO users VALUES (4, 1 + 1);", Error::TypeMismatch("column \"name\" is text, got int value 2".to_string())),
+	("INSERT INTO users (id, missing) VALUES (4, 'x');", Error::ColumnDoesNotExist("missing".to_string())),
+	("INSERT INTO users (id, id) VALUES (4, 5);", Error::InvalidExpression("column \"id\" specified more than once".to_string())),
+	("INSERT INTO users (id) VALUES (4, 'x');", Error::ArityMismatch{expected: 1, got: 2}),
+	("INSERT INTO users VALUES (3.5, 'x');", Error::InvalidValue("3.5 is not a valid int".to_string())),
+	("SELECT 1e10;", Error::InvalidValue("1e10 is not a valid int".to_string())),
+	("SELECT -2147483649;", Error::InvalidValue("-2147483649 is not a valid int".to_string())),
+	("CREATE TABLE t (a TABLE);", Error::UnsupportedType("table".to_string())),
+	("UPDATE users SET missing = 1;", Error::ColumnDoesNotExist("missing".to_string())),
+	("UPDATE users SET id = 'x' WHERE id = 1;", Error::TypeMismatch("column \"id\" is int, got text value x".to_string())),
+	("UPDATE orders SET id = 1;", Error::TableDoesNotExist("orders".to_string())),
+	("DELETE FROM users WHERE name;", Error::TypeMismatch("condition must be boolean, got text".to_string())),
+	("DELETE FROM orders;", Error::TableDoesNotExist("orders".to_string())),
+	("CREATE TABLE users (id INT);", Error::TableAlreadyExists("users".to_string())),
+	("DROP TABLE orders;", Error::TableDoesNotExist("orders".to_string())),
+	("DROP TABLE users; SELECT id FROM users;", Error::TableDoesNotExist("users".to_string())),
+	("ALTER TABLE users ADD COLUMN name TEXT;", Error::ColumnAlreadyExists("name".to_string())),
+	("ALTER TABLE users ADD age INT NOT NULL;", Error::NotNullViolation("age".to_string())),
+	("ALTER TABLE users DROP COLUMN age;", Error::ColumnDoesNotExist("age".to_string())),
+	("ALTER TABLE users RENAME COLUMN id TO name;", Error::ColumnAlreadyExists("name".to_string())),
+	("CREATE TABLE t (a INT); ALTER TABLE t RENAME TO users;", Error::TableAlreadyExists("users".to_string())),
+	("CREATE TABLE t (a INT, a TEXT);", Error::ColumnAlreadyExists("a".to_string())),
+	("SELECT id FROM users ORDER BY missing;", Error::ColumnDoesNotExist("missing".to_string())),
+	("SELECT id FROM users ORDER BY 2;", Error::InvalidExpression("ORDER BY position 2 is not in select list".to_string())),
+	("SELECT id FROM users ORDER BY 0;", Error::InvalidExpression("ORDER BY position 0 is not in select list".to_string())),
+	("SELECT id FROM users LIMIT -1;", Error::InvalidValue("LIMIT must not be negative".to_string())),
+	("SELECT id FROM users LIMIT 1 OFFSET 'a';", Error::TypeMismatch("OFFSET must be int, got text".to_string())),
+	("SELECT id FROM users LIMIT id;", Error::ColumnDoesNotExist("id".to_string())),
+	("SELECT id, COUNT(*) FROM users;", Error::InvalidExpression("column \"id\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT id FROM users WHERE COUNT(*) > 1;", Error::InvalidExpression("aggregate function count is not allowed here".to_string())),
+	("SELECT SUM(COUNT(*)) FROM users;", Error::InvalidExpression("aggregate function count is not allowed here".to_string())),
+	("SELECT SUM(name) FROM users;", Error::TypeMismatch("sum(text)".to_string())),
+	("SELECT MAX(*) FROM users;", Error::InvalidExpression("max(*) is not supported".to_string())),
+	("SELECT MIN(id, name) FROM users;", Error::InvalidExpression("min takes exactly one argument".to_string())),
+	("SELECT LOWER(name) FROM users;", Error::InvalidExpression("function lower does not exist".to_string())),
+	("SELECT id, name FROM users GROUP BY id;", Error::InvalidExpression("column \"name\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT id + 1 FROM users GROUP BY id + 2;", Error::InvalidExpression("column \"id\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT COUNT(*) FROM users HAVING name = 'bob';", Error::InvalidExpression("column \"name\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT id, (SELECT name) FROM users GROUP BY id;", Error::InvalidExpression("column \"name\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT id FROM users GROUP BY id HAVING EXISTS (SELECT 1 WHERE name = 'bob');", Error::InvalidExpression("column \"name\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT * FROM users GROUP BY id;", Error::InvalidExpression("column \"name\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("SELECT COUNT(*) FROM users GROUP BY missing;", Error::ColumnDoesNotExist("missing".to_string())),
+	("SELECT COUNT(*) FROM users GROUP BY COUNT(*);", Error::InvalidExpression("aggregate function count is not allowed here".to_string())),
+	("SELECT DISTINCT name FROM users ORDER BY id;", Error::InvalidExpression("for SELECT DISTINCT, ORDER BY expressions must appear in select list".to_string())),
+	("SELECT id FROM users WHERE NULL + 'a' = 1;", Error::TypeMismatch("unknown + text".to_string())),
+	("SELECT id FROM users a, users b;", Error::AmbiguousColumn("id".to_string())),
+	("SELECT users.id FROM users u;", Error::ColumnDoesNotExist("users.id".to_string())),
+	("SELECT id FROM users, users;", Error::InvalidExpression("table name \"users\" specified more than once".to_string())),
+	("SELECT x.* FROM users;", Error::TableDoesNotExist("x".to_string())),
+	("SELECT id FROM users JOIN missing ON true;", Error::TableDoesNotExist("missing".to_string())),
+	("SELECT a.id FROM users a JOIN users b ON a.name;", Error::TypeMismatch("JOIN condition must be boolean, got text".to_string())),
+	("UPDATE users SET name = 'x' WHERE users.id = 1; SELECT u.id FROM users;", Error::ColumnDoesNotExist("u.id".to_string())),
+	("SELECT (SELECT id, name FROM users);", Error::InvalidExpression("subquery must return only one column".to_string())),
+	("SELECT (SELECT id FROM users);", Error::InvalidValue("more than one row returned by a subquery used as an expression".to_string())),
+	("SELECT id FROM users WHERE name IN (SELECT id FROM users);", Error::TypeMismatch("text in int".to_string())),
+	("SELECT id FROM users WHERE EXISTS (SELECT missing FROM users);", Error::ColumnDoesNotExist("missing".to_string())),
+	("SELECT id FROM users LIMIT (SELECT 1);", Error::InvalidExpression("subquery is not allowed here".to_string())),
+	("SELECT x.id FROM (SELECT 1) AS x;", Error::ColumnDoesNotExist("x.id".to_string())),
+	("SELECT id FROM users UNION SELECT id, name FROM users;", Error::InvalidExpression("each UNION query must have the same number of columns".to_string())),
+	("SELECT id FROM users EXCEPT SELECT name FROM users;", Error::TypeMismatch("EXCEPT types int and text cannot be matched".to_string())),
+	("SELECT id FROM users UNION SELECT id FROM users ORDER BY name;", Error::ColumnDoesNotExist("name".to_string())),
+	("SELECT id FROM users UNION SELECT id FROM users ORDER BY 2;", Error::InvalidExpression("ORDER BY position 2 is not in select list".to_string())),
+	("WITH a AS (SELECT 1), a AS (SELECT 2) SELECT 1;", Error::InvalidExpression("WITH query name \"a\" specified more than once".to_string())),
+	("WITH a (x, y) AS (SELECT 1) SELECT x FROM a;", Error::InvalidExpression("WITH query \"a\" has 1 columns available but 2 columns specified".to_string())),
+	("WITH a AS (SELECT 1 AS x UNION SELECT x FROM a) SELECT x FROM a;", Error::TableDoesNotExist("a".to_string())),
+	("WITH a AS (SELECT 1 AS x) SELECT x FROM a; SELECT x FROM a;", Error::TableDoesNotExist("a".to_string())),
+	("WITH RECURSIVE a (x) AS (SELECT 1 INTERSECT SELECT x FROM a) SELECT x FROM a;", Error::InvalidExpression("recursive query \"a\" must be a UNION of a non-recursive term and a recursive term".to_string())),
+	("WITH RECURSIVE a (x) AS (SELECT 1 UNION SELECT x + 1 FROM a ORDER BY x) SELECT x FROM a;", Error::InvalidExpression("ORDER BY, LIMIT and OFFSET are not supported in recursive query \"a\"".to_string())),
+	("WITH RECURSIVE a (x) AS (SELECT 1 UNION SELECT name FROM a, users) SELECT x FROM a;", Error::TypeMismatch("recursive query \"a\" column 1 has type int in its non-recursive term but type text overall".to_string())),
+	("SELECT id FROM users WHERE ROW_NUMBER() OVER () = 1;", Error::InvalidExpression("window functions are not allowed in WHERE".to_string())),
+	("SELECT COUNT(*) FROM users GROUP BY RANK() OVER (ORDER BY id);", Error::InvalidExpression("window functions are not allowed in GROUP BY".to_string())),
+	("SELECT ROW_NUMBER() FROM users;", Error::InvalidExpression("window function row_number requires an OVER clause".to_string())),
+	("SELECT SUM(ROW_NUMBER() OVER ()) FROM users;", Error::InvalidExpression("aggregate function calls cannot contain window function calls".to_string())),
+	("SELECT LAG(RANK() OVER ()) OVER () FROM users;", Error::InvalidExpression("window function calls cannot be nested".to_string())),
+	("SELECT RANK(id) OVER () FROM users;", Error::InvalidExpression("rank takes no arguments".to_string())),
+	("SELECT LEAD() OVER () FROM users;", Error::InvalidExpression("lead takes one to three arguments".to_string())),
+	("SELECT LAG(name, 'a') OVER () FROM users;", Error::TypeMismatch("lag offset must be int, got text".to_string())),
+	("SELECT LAG(name, 1, 0) OVER () FROM users;", Error::TypeMismatch("lag default must be text, got int".to_string())),
+	("SELECT COUNT(DISTINCT id) OVER () FROM users;", Error::InvalidExpression("DISTINCT is not supported for window functions".to_string())),
+	("SELECT SUM(id) OVER (ORDER BY id RANGE 1 PRECEDING) FROM users;", Error::InvalidExpression("RANGE with offset PRECEDING or FOLLOWING is not supported".to_string())),
+	("SELECT SUM(id) OVER (ROWS -1 PRECEDING) FROM users;", Error::InvalidValue("FRAME OFFSET must not be negative".to_string())),
+	("SELECT id, ROW_NUMBER() OVER (ORDER BY name) FROM users GROUP BY id;", Error::InvalidExpression("column \"name\" must appear in the GROUP BY clause or be used in an aggregate function".to_string())),
+	("UPDATE users SET id = ROW_NUMBER() OVER ();", Error::InvalidExpression("window function row_number is not allowed here".to_string())),
+})]
+fn execute_error(case: (&str, Error)) {
+	let (source, err) = case;
+	let mut backend = setup();
+	assert_eq!(execute(&mut backend, source).err().unwrap(), err);
+}
+
+#[test]
+fn insert_expressions() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "INSERT INTO users VALUES (2 * 2, 'da' || 've');
+		SELECT id, name FROM users WHERE id = 4;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"4", "dave"}});
+}
+
+#[test]
+fn insert_column_list_and_rows() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "INSERT INTO users (name, id) VALUES ('dave', 4), ('erin', 5);
+		INSERT INTO users (id) VALUES (6);
+		SELECT id, name FROM users WHERE id > 3;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"4", "dave"}, vec!{"5", "erin"}, vec!{"6", "NULL"}});
+}
+
+#[test]
+fn insert_rows_are_atomic() {
+	let mut backend = setup();
+	assert!(execute(&mut backend, "INSERT INTO users VALUES (4, 'dave'), ('x', 'erin');").is_err());
+	let results = execute(&mut backend, "SELECT id FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"1"}, vec!{"2"}, vec!{"3"}});
+}
+
+#[test]
+fn select_asterisk_columns() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "SELECT * FROM users;").unwrap().unwrap();
+	let columns: Vec<(&str, ColumnType)> = results.columns.iter().map(|c| (&c.name[..], c.col_type.clone())).collect();
+	assert_eq!(columns, vec!{("id", ColumnType::IntType), ("name", ColumnType::TextType)});
+}
+
+#[test]
+fn boolean_column() {
+	let mut backend = MemoryBackend::new();
+	let results = execute(&mut backend, "CREATE TABLE flags (id INT, active BOOLEAN);
+		INSERT INTO flags VALUES (1, true);
+		INSERT INTO flags VALUES (2, FALSE);
+		INSERT INTO flags VALUES (3, 1 < 2);
+		SELECT id, active FROM flags WHERE active;").unwrap().unwrap();
+	assert_eq!(results.columns[1].col_type, ColumnType::BoolType);
+	assert_eq!(render(&results), vec!{vec!{"1", "true"}, vec!{"3", "true"}});
+}
+
+#[test]
+fn select_aliases() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "SELECT id AS user_id, name AS n, id * 10 AS score, id + 1 FROM users;").unwrap().unwrap();
+	let names: Vec<&str> = results.columns.iter().map(|c| &c.name[..]).collect();
+	assert_eq!(names, vec!{"user_id", "n", "score", "?column?"});
+	assert_eq!(render(&results)[0], vec!{"1", "alice", "10", "2"});
+}
+
+#[test]
+fn null_values() {
+	let mut backend = setup();
+	execute(&mut backend, "INSERT INTO users VALUES (4, NULL); INSERT INTO users (id) VALUES (5);").unwrap();
+
+	let results = execute(&mut backend, "SELECT id, name IS NULL, name IS NOT NULL FROM users WHERE id > 3;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"4", "true", "false"}, vec!{"5", "true", "false"}});
+
+	// NULL never satisfies a comparison, not even with itself.
+	let results = execute(&mut backend, "SELECT id FROM users WHERE name = NULL OR name <> 'alice';").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"2"}, vec!{"3"}});
+
+	let results = execute(&mut backend, "SELECT id + NULL, name || 'x', NULL FROM users WHERE id = 4;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"NULL", "NULL", "NULL"}});
+}
+
+#[parameterized(case = {
+	("NULL AND true", "NULL"),
+	("NULL AND false", "false"),
+	("false AND NULL", "false"),
+	("NULL OR true", "true"),
+	("true OR NULL", "true"),
+	("NULL OR false", "NULL"),
+	("NOT NULL", "NULL"),
+	("NOT (NULL IS NULL)", "false"),
+	("1 = 1 IS NOT NULL", "true"),
+})]
+fn three_valued_logic(case: (&str, &str)) {
+	let (exp, value) = case;
+	let mut backend = MemoryBackend::new();
+	let results = execute(&mut backend, &format!("SELECT {};", exp)).unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{value}});
+}
+
+#[test]
+fn not_null_columns() {
+	let mut backend = MemoryBackend::new();
+	execute(&mut backend, "CREATE TABLE t (a INT NOT NULL, b TEXT NULL);").unwrap();
+	execute(&mut backend, "INSERT INTO t (a) VALUES (1);").unwrap();
+	assert_eq!(execute(&mut backend, "INSERT INTO t VALUES (NULL, 'x');").err().unwrap(), Error::NotNullViolation("a".to_string()));
+	assert_eq!(execute(&mut backend, "INSERT INTO t (b) VALUES ('x');").err().unwrap(), Error::NotNullViolation("a".to_string()));
+}
+
+#[test]
+fn update_rows() {
+	let mut backend = setup();
+	let stmt = parse("UPDATE users SET id = id * 10, name = name || '!' WHERE id >= 2;").unwrap().statements.remove(0);
+	assert_eq!(backend.update(&stmt.update_statement.unwrap()).unwrap(), 2);
+
+	let results = execute(&mut backend, "SELECT id, name FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"1", "alice"}, vec!{"20", "bob!"}, vec!{"30", "carol!"}});
+}
+
+#[test]
+fn update_is_atomic() {
+	let mut backend = MemoryBackend::new();
+	execute(&mut backend, "CREATE TABLE t (a INT, b INT NOT NULL); INSERT INTO t VALUES (1, 1), (2, 2);").unwrap();
+	assert_eq!(execute(&mut backend, "UPDATE t SET b = NULL WHERE a = 2;").err().unwrap(), Error::NotNullViolation("b".to_string()));
+	assert_eq!(execute(&mut backend, "UPDATE t SET a = 10 / (a - 2);").err().unwrap(), Error::DivisionByZero);
+
+	let results = execute(&mut backend, "SELECT a, b FROM t;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"1", "1"}, vec!{"2", "2"}});
+}
+
+#[test]
+fn delete_rows() {
+	let mut backend = setup();
+	let stmt = parse("DELETE FROM users WHERE id <> 2;").unwrap().statements.remove(0);
+	assert_eq!(backend.delete(&stmt.delete_statement.unwrap()).unwrap(), 2);
+
+	let results = execute(&mut backend, "SELECT id, name FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"2", "bob"}});
+
+	let results = execute(&mut backend, "DELETE FROM users; INSERT INTO users VALUES (7, 'gus'); SELECT id FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"7"}});
+}
+
+#[test]
+fn create_and_drop_if_exists() {
+	let mut backend = setup();
+	let stmt = parse("CREATE TABLE IF NOT EXISTS users (a BOOLEAN);").unwrap().statements.remove(0);
+	assert!(!backend.create_table(&stmt.create_table_statement.unwrap()).unwrap());
+
+	// The existing table and its rows are left alone.
+	let results = execute(&mut backend, "SELECT id FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"1"}, vec!{"2"}, vec!{"3"}});
+
+	let stmt = parse("DROP TABLE IF EXISTS orders;").unwrap().statements.remove(0);
+	assert!(!backend.drop_table(&stmt.drop_table_statement.unwrap()).unwrap());
+
+	let results = execute(&mut backend, "DROP TABLE IF EXISTS users; CREATE TABLE users (a BOOLEAN);
+		INSERT INTO users VALUES (true); SELECT a FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"true"}});
+}
+
+#[test]
+fn alter_table() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "ALTER TABLE users ADD COLUMN active BOOLEAN;
+		UPDATE users SET active = id <> 2;
+		ALTER TABLE users DROP id;
+		ALTER TABLE users RENAME COLUMN name TO login;
+		ALTER TABLE users RENAME TO accounts;
+		SELECT * FROM accounts;").unwrap().unwrap();
+	assert_eq!(results.columns.iter().map(|c| c.name.clone()).collect::<Vec<String>>(), vec!{"login", "active"});
+	assert_eq!(render(&results), vec!{vec!{"alice", "true"}, vec!{"bob", "false"}, vec!{"carol", "true"}});
+	assert_eq!(execute(&mut backend, "SELECT login FROM users;").err().unwrap(), Error::TableDoesNotExist("users".to_string()));
+}
+
+#[parameterized(case = {
+	("SELECT id FROM users ORDER BY name DESC;", vec!{vec!{"4"}, vec!{"3"}, vec!{"2"}, vec!{"1"}}),
+	("SELECT name AS n FROM users ORDER BY n DESC;", vec!{vec!{"dave"}, vec!{"carol"}, vec!{"bob"}, vec!{"alice"}}),
+	("SELECT id FROM users ORDER BY -id;", vec!{vec!{"4"}, vec!{"3"}, vec!{"2"}, vec!{"1"}}),
+	("SELECT id FROM users ORDER BY name = 'bob' DESC, id;", vec!{vec!{"2"}, vec!{"1"}, vec!{"3"}, vec!{"4"}}),
+	("SELECT id FROM users ORDER BY score;", vec!{vec!{"2"}, vec!{"4"}, vec!{"1"}, vec!{"3"}}),
+	("SELECT id FROM users ORDER BY score DESC, id DESC;", vec!{vec!{"3"}, vec!{"1"}, vec!{"4"}, vec!{"2"}}),
+	("SELECT id FROM users ORDER BY score NULLS FIRST;", vec!{vec!{"1"}, vec!{"3"}, vec!{"2"}, vec!{"4"}}),
+	("SELECT id FROM users ORDER BY score DESC NULLS LAST, id DESC;", vec!{vec!{"4"}, vec!{"2"}, vec!{"3"}, vec!{"1"}}),
+	("SELECT id FROM users ORDER BY id DESC LIMIT 2;", vec!{vec!{"4"}, vec!{"3"}}),
+	("SELECT id FROM users ORDER BY id DESC LIMIT 2 OFFSET 1;", vec!{vec!{"3"}, vec!{"2"}}),
+	("SELECT name, id FROM users ORDER BY 2 DESC;", vec!{vec!{"dave", "4"}, vec!{"carol", "3"}, vec!{"bob", "2"}, vec!{"alice", "1"}}),
+	("SELECT id, score FROM users ORDER BY 2, 1 DESC;", vec!{vec!{"4", "10"}, vec!{"2", "10"}, vec!{"3", "NULL"}, vec!{"1", "NULL"}}),
+	("SELECT id FROM users WHERE id > 1 LIMIT 1 + 1;", vec!{vec!{"2"}, vec!{"3"}}),
+	("SELECT id FROM users OFFSET 3;", vec!{vec!{"4"}}),
+	("SELECT id FROM users LIMIT 0;", vec!{}),
+	("SELECT id FROM users LIMIT NULL OFFSET 10;", vec!{}),
+})]
+fn order_by_and_limit(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup();
+	execute(&mut backend, "ALTER TABLE users ADD COLUMN score INT;
+		UPDATE users SET score = 10 WHERE id = 2;
+		INSERT INTO users VALUES (4, 'dave', 10);").unwrap();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[parameterized(case = {
+	("SELECT COUNT(*), COUNT(score), SUM(score), AVG(score) FROM users;", vec!{vec!{"4", "3", "16", "5"}}),
+	("SELECT MIN(name), MAX(name), MIN(score), MAX(score) FROM users;", vec!{vec!{"alice", "dave", "2", "9"}}),
+	("SELECT COUNT(*) * 10 + 1 FROM users WHERE id > 2;", vec!{vec!{"21"}}),
+	("SELECT COUNT(*), SUM(score), MAX(name) FROM users WHERE id > 10;", vec!{vec!{"0", "NULL", "NULL"}}),
+	("SELECT COUNT(*);", vec!{vec!{"1"}}),
+	("SELECT AVG(id), AVG(-id) FROM users WHERE id < 3;", vec!{vec!{"1", "-1"}}),
+})]
+fn aggregates(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup();
+	execute(&mut backend, "ALTER TABLE users ADD COLUMN score INT;
+		UPDATE users SET score = id * 2 + 3 WHERE id <> 2;
+		INSERT INTO users VALUES (4, 'dave', 2);").unwrap();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[test]
+fn aggregate_columns() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "SELECT COUNT(*), MAX(name) AS last FROM users;").unwrap().unwrap();
+	assert_eq!(results.columns.iter().map(|c| (c.name.clone(), c.col_type.clone())).collect::<Vec<(String, ColumnType)>>(),
+		vec!{("count".to_string(), ColumnType::IntType), ("last".to_string(), ColumnType::TextType)});
+}
+
+#[parameterized(case = {
+	("SELECT dept, COUNT(*) FROM emp GROUP BY dept;", vec!{vec!{"eng", "3"}, vec!{"ops", "2"}, vec!{"NULL", "1"}}),
+	("SELECT dept, SUM(salary) FROM emp GROUP BY dept HAVING COUNT(*) > 1 ORDER BY SUM(salary);", vec!{vec!{"ops", "70"}, vec!{"eng", "330"}}),
+	("SELECT dept, MAX(salary) AS top FROM emp WHERE salary < 120 GROUP BY dept ORDER BY top DESC;", vec!{vec!{"eng", "110"}, vec!{"NULL", "90"}, vec!{"ops", "40"}}),
+	("SELECT salary > 60, COUNT(*) FROM emp GROUP BY salary > 60;", vec!{vec!{"true", "4"}, vec!{"false", "2"}}),
+	("SELECT dept IS NULL, COUNT(dept) FROM emp GROUP BY dept IS NULL;", vec!{vec!{"false", "5"}, vec!{"true", "0"}}),
+	("SELECT dept FROM emp GROUP BY dept HAVING MIN(salary) > 50 LIMIT 1;", vec!{vec!{"eng"}}),
+	("SELECT dept FROM emp WHERE salary > 1000 GROUP BY dept;", vec!{}),
+	("SELECT COUNT(*) FROM emp HAVING COUNT(*) > 10;", vec!{}),
+	("SELECT DISTINCT dept FROM emp;", vec!{vec!{"eng"}, vec!{"ops"}, vec!{"NULL"}}),
+	("SELECT DISTINCT dept, salary > 60 FROM emp ORDER BY dept DESC;", vec!{vec!{"NULL", "true"}, vec!{"ops", "false"}, vec!{"eng", "true"}}),
+	("SELECT DISTINCT salary / 100 FROM emp LIMIT 2;", vec!{vec!{"1"}, vec!{"0"}}),
+	("SELECT COUNT(DISTINCT dept), COUNT(dept), SUM(DISTINCT salary / 100) FROM emp;", vec!{vec!{"2", "5", "1"}}),
+	("SELECT DISTINCT COUNT(*) FROM emp GROUP BY dept;", vec!{vec!{"3"}, vec!{"2"}, vec!{"1"}}),
+	("SELECT DISTINCT * FROM emp ORDER BY emp.salary LIMIT 1;", vec!{vec!{"ben", "ops", "30"}}),
+})]
+fn group_by_and_distinct(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = MemoryBackend::new();
+	execute(&mut backend, "CREATE TABLE emp (name TEXT, dept TEXT, salary INT);
+		INSERT INTO emp VALUES
+			('ann', 'eng', 100), ('ben', 'ops', 30), ('cat', 'eng', 120),
+			('dan', NULL, 90), ('eve', 'ops', 40), ('fay', 'eng', 110);").unwrap();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[parameterized(case = {
+	("SELECT name, total FROM users JOIN orders ON users.id = orders.user_id ORDER BY total;", vec!{vec!{"alice", "5"}, vec!{"bob", "7"}, vec!{"alice", "10"}}),
+	("SELECT u.name, o.total FROM users u INNER JOIN orders AS o ON o.user_id = u.id AND o.total > 6;", vec!{vec!{"alice", "10"}, vec!{"bob", "7"}}),
+	("SELECT name, total FROM users LEFT JOIN orders ON id = user_id ORDER BY id, total;", vec!{vec!{"alice", "5"}, vec!{"alice", "10"}, vec!{"bob", "7"}, vec!{"carol", "NULL"}}),
+	("SELECT name, total FROM users LEFT OUTER JOIN orders ON id = user_id AND total < 6;", vec!{vec!{"alice", "5"}, vec!{"bob", "NULL"}, vec!{"carol", "NULL"}}),
+	("SELECT COUNT(*) FROM users, orders;", vec!{vec!{"12"}}),
+	("SELECT a.name, b.name FROM users a CROSS JOIN users b WHERE a.id < b.id;", vec!{vec!{"alice", "bob"}, vec!{"alice", "carol"}, vec!{"bob", "carol"}}),
+	("SELECT users.name, COUNT(total) FROM users LEFT JOIN orders ON id = user_id GROUP BY name;", vec!{vec!{"alice", "2"}, vec!{"bob", "1"}, vec!{"carol", "0"}}),
+	("SELECT orders.* FROM users JOIN orders ON users.id = orders.user_id WHERE name = 'bob';", vec!{vec!{"2", "7"}}),
+	("SELECT u.name, p.name FROM users u JOIN orders o ON u.id = o.user_id JOIN users p ON o.total = p.id * 5;", vec!{vec!{"alice", "bob"}, vec!{"alice", "alice"}}),
+})]
+fn joins(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup_orders();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[test]
+fn join_columns() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "CREATE TABLE orders (user_id INT, total INT);
+		SELECT u.*, total FROM users u JOIN orders ON u.id = user_id;").unwrap().unwrap();
+	let columns: Vec<(&str, Option<&str>)> = results.columns.iter().map(|c| (&c.name[..], c.table.as_deref())).collect();
+	assert_eq!(columns, vec!{("id", Some("u")), ("name", Some("u")), ("total", Some("orders"))});
+}
+
+#[parameterized(case = {
+	("SELECT name FROM users WHERE id IN (SELECT user_id FROM orders);", vec!{vec!{"alice"}, vec!{"bob"}}),
+	("SELECT name FROM users WHERE id NOT IN (SELECT user_id FROM orders WHERE user_id IS NOT NULL);", vec!{vec!{"carol"}}),
+	("SELECT name FROM users WHERE id NOT IN (SELECT user_id FROM orders);", vec!{}),
+	("SELECT id IN (SELECT user_id FROM orders) FROM users;", vec!{vec!{"true"}, vec!{"true"}, vec!{"NULL"}}),
+	("SELECT name FROM users WHERE EXISTS (SELECT 1 FROM orders WHERE user_id = id AND total > 6);", vec!{vec!{"alice"}, vec!{"bob"}}),
+	("SELECT name FROM users u WHERE NOT EXISTS (SELECT * FROM orders o WHERE o.user_id = u.id);", vec!{vec!{"carol"}}),
+	("SELECT name, (SELECT SUM(total) FROM orders WHERE user_id = users.id) FROM users;", vec!{vec!{"alice", "15"}, vec!{"bob", "7"}, vec!{"carol", "NULL"}}),
+	("SELECT (SELECT MAX(total) FROM orders) - id FROM users WHERE id = 1;", vec!{vec!{"9"}}),
+	("SELECT name FROM users WHERE (SELECT COUNT(*) FROM orders WHERE user_id = id) = 0;", vec!{vec!{"carol"}}),
+	("SELECT sub.name, sub.n FROM (SELECT name, id * 10 AS n FROM users) AS sub WHERE n > 10;", vec!{vec!{"bob", "20"}, vec!{"carol", "30"}}),
+	("SELECT s.user_id, s.c FROM (SELECT user_id, COUNT(*) AS c FROM orders GROUP BY user_id) s JOIN users ON users.id = s.user_id ORDER BY s.c DESC;", vec!{vec!{"1", "2"}, vec!{"2", "1"}}),
+	("SELECT user_id, COUNT(*) FROM orders GROUP BY user_id HAVING COUNT(*) > (SELECT COUNT(*) FROM users WHERE id = user_id);", vec!{vec!{"1", "2"}, vec!{"NULL", "1"}}),
+	("SELECT (SELECT name FROM users WHERE id = 5);", vec!{vec!{"NULL"}}),
+	("SELECT name FROM users WHERE id IN (SELECT user_id FROM orders WHERE total IN (SELECT id * 5 FROM users));", vec!{vec!{"alice"}}),
+	("SELECT * FROM (SELECT 1, 2) s;", vec!{vec!{"1", "2"}}),
+	("SELECT * FROM (SELECT user_id, user_id FROM orders WHERE total > 6) s;", vec!{vec!{"1", "1"}, vec!{"2", "2"}}),
+})]
+fn subqueries(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup_orders();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[test]
+fn update_and_delete_with_subqueries() {
+	let mut backend = setup();
+	let results = execute(&mut backend, "CREATE TABLE orders (user_id INT, total INT);
+		INSERT INTO orders VALUES (1, 10), (2, 7), (1, 5);
+		UPDATE users SET name = name || '!' WHERE EXISTS (SELECT 1 FROM orders WHERE user_id = users.id AND total > 8);
+		DELETE FROM users WHERE id NOT IN (SELECT user_id FROM orders);
+		SELECT id, name FROM users;").unwrap().unwrap();
+	assert_eq!(render(&results), vec!{vec!{"1", "alice!"}, vec!{"2", "bob"}});
+}
+
+#[parameterized(case = {
+	("SELECT id FROM users UNION SELECT user_id FROM orders;", vec!{vec!{"1"}, vec!{"2"}, vec!{"3"}, vec!{"NULL"}}),
+	("SELECT user_id FROM orders UNION ALL SELECT id FROM users WHERE id > 2;", vec!{vec!{"1"}, vec!{"2"}, vec!{"1"}, vec!{"NULL"}, vec!{"3"}}),
+	("SELECT id FROM users INTERSECT SELECT user_id FROM orders;", vec!{vec!{"1"}, vec!{"2"}}),
+	("SELECT user_id FROM orders INTERSECT ALL SELECT 1 UNION ALL SELECT 1;", vec!{vec!{"1"}, vec!{"1"}}),
+	("SELECT user_id FROM orders INTERSECT ALL SELECT 1;", vec!{vec!{"1"}}),
+	("SELECT id FROM users EXCEPT SELECT user_id FROM orders;", vec!{vec!{"3"}}),
+	("SELECT user_id FROM orders EXCEPT ALL SELECT 1;", vec!{vec!{"2"}, vec!{"1"}, vec!{"NULL"}}),
+	("SELECT NULL UNION SELECT NULL;", vec!{vec!{"NULL"}}),
+	("SELECT id, name FROM users UNION SELECT user_id, NULL FROM orders ORDER BY id DESC NULLS LAST, name LIMIT 3;", vec!{vec!{"3", "carol"}, vec!{"2", "bob"}, vec!{"2", "NULL"}}),
+	("SELECT id FROM users UNION SELECT 4 EXCEPT SELECT 1 ORDER BY id OFFSET 1;", vec!{vec!{"3"}, vec!{"4"}}),
+	("SELECT id FROM users UNION SELECT 4 ORDER BY 1 DESC;", vec!{vec!{"4"}, vec!{"3"}, vec!{"2"}, vec!{"1"}}),
+	("SELECT id FROM users EXCEPT SELECT 1 INTERSECT SELECT 2;", vec!{vec!{"1"}, vec!{"2"}, vec!{"3"}}),
+	("SELECT name FROM users WHERE id IN (SELECT 1 UNION SELECT 3);", vec!{vec!{"alice"}, vec!{"carol"}}),
+	("SELECT COUNT(*) FROM (SELECT user_id FROM orders UNION SELECT id FROM users) AS ids;", vec!{vec!{"4"}}),
+})]
+fn set_operations(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup_orders();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[parameterized(case = {
+	("WITH big AS (SELECT id, name FROM users WHERE id > 1) SELECT name FROM big;", vec!{vec!{"bob"}, vec!{"carol"}}),
+	("WITH t (n, label) AS (SELECT id, name FROM users) SELECT label FROM t WHERE n = 3;", vec!{vec!{"carol"}}),
+	("WITH a AS (SELECT id FROM users), b AS (SELECT id * 2 AS id FROM a) SELECT SUM(id) FROM b;", vec!{vec!{"12"}}),
+	("WITH users AS (SELECT 9 AS id) SELECT id FROM users;", vec!{vec!{"9"}}),
+	("WITH bosses AS (SELECT manager FROM staff WHERE manager IS NOT NULL) SELECT name FROM staff WHERE id NOT IN (SELECT manager FROM bosses) ORDER BY name;", vec!{vec!{"cat"}, vec!{"eve"}}),
+	("WITH RECURSIVE n (i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 5) SELECT SUM(i) FROM n;", vec!{vec!{"15"}}),
+	("WITH RECURSIVE chain (id, name, depth) AS (SELECT id, name, 0 FROM staff WHERE manager IS NULL UNION ALL SELECT s.id, s.name, c.depth + 1 FROM staff s JOIN chain c ON s.manager = c.id) SELECT name, depth FROM chain ORDER BY depth, name;", vec!{vec!{"ann", "0"}, vec!{"ben", "1"}, vec!{"cat", "1"}, vec!{"dan", "2"}, vec!{"eve", "3"}}),
+	("WITH RECURSIVE up (id) AS (SELECT 5 UNION SELECT manager FROM staff JOIN up ON staff.id = up.id WHERE manager IS NOT NULL) SELECT name FROM staff WHERE id IN (SELECT id FROM up) ORDER BY id;", vec!{vec!{"ann"}, vec!{"ben"}, vec!{"dan"}, vec!{"eve"}}),
+	("WITH RECURSIVE r (x) AS (SELECT 1 UNION SELECT 3 - x FROM r) SELECT x FROM r;", vec!{vec!{"1"}, vec!{"2"}}),
+	("WITH RECURSIVE a AS (SELECT 1 AS x) SELECT x FROM a;", vec!{vec!{"1"}}),
+	("WITH t AS (SELECT 1 + 1, 2 + 2) SELECT * FROM t;", vec!{vec!{"2", "4"}}),
+})]
+fn common_table_expressions(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = setup();
+	execute(&mut backend, "CREATE TABLE staff (id INT, name TEXT, manager INT);
+		INSERT INTO staff VALUES (1, 'ann', NULL), (2, 'ben', 1), (3, 'cat', 1), (4, 'dan', 2), (5, 'eve', 4);").unwrap();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
+
+#[parameterized(case = {
+	("SELECT name, ROW_NUMBER() OVER (ORDER BY points DESC, name) FROM scores;", vec!{vec!{"ann", "4"}, vec!{"ben", "1"}, vec!{"cat", "3"}, vec!{"dan", "2"}, vec!{"eve", "5"}}),
+	("SELECT name, RANK() OVER (ORDER BY points DESC), DENSE_RANK() OVER (ORDER BY points DESC) FROM scores ORDER BY name;", vec!{vec!{"ann", "4", "3"}, vec!{"ben", "1", "1"}, vec!{"cat", "3", "2"}, vec!{"dan", "1", "1"}, vec!{"eve", "5", "4"}}),
+	("SELECT team, name, ROW_NUMBER() OVER (PARTITION BY team ORDER BY points) AS n FROM scores ORDER BY team, n;", vec!{vec!{"blue", "eve", "1"}, vec!{"blue", "cat", "2"}, vec!{"red", "ann", "1"}, vec!{"red", "ben", "2"}, vec!{"red", "dan", "3"}}),
+	("SELECT name, LAG(points) OVER (ORDER BY name), LEAD(points, 2, 0) OVER (ORDER BY name) FROM scores ORDER BY name;", vec!{vec!{"ann", "NULL", "15"}, vec!{"ben", "10", "20"}, vec!{"cat", "20", "5"}, vec!{"dan", "15", "0"}, vec!{"eve", "20", "0"}}),
+	("SELECT name, SUM(points) OVER (ORDER BY name) FROM scores ORDER BY name;", vec!{vec!{"ann", "10"}, vec!{"ben", "30"}, vec!{"cat", "45"}, vec!{"dan", "65"}, vec!{"eve", "70"}}),
+	("SELECT name, SUM(points) OVER (ORDER BY points) FROM scores ORDER BY points, name;", vec!{vec!{"eve", "5"}, vec!{"ann", "15"}, vec!{"cat", "30"}, vec!{"ben", "70"}, vec!{"dan", "70"}}),
+	("SELECT name, SUM(points) OVER (ORDER BY points ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) FROM scores ORDER BY points, name;", vec!{vec!{"eve", "15"}, vec!{"ann", "30"}, vec!{"cat", "45"}, vec!{"ben", "55"}, vec!{"dan", "40"}}),
+	("SELECT name, SUM(points) OVER (ORDER BY name ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) FROM scores ORDER BY name;", vec!{vec!{"ann", "70"}, vec!{"ben", "60"}, vec!{"cat", "40"}, vec!{"dan", "25"}, vec!{"eve", "5"}}),
+	("SELECT name, COUNT(*) OVER (ORDER BY points ROWS UNBOUNDED PRECEDING), MAX(points) OVER (PARTITION BY team) FROM scores ORDER BY points, name;", vec!{vec!{"eve", "1", "15"}, vec!{"ann", "2", "20"}, vec!{"cat", "3", "15"}, vec!{"ben", "4", "20"}, vec!{"dan", "5", "20"}}),
+	("SELECT name, points - AVG(points) OVER (PARTITION BY team) FROM scores ORDER BY name;", vec!{vec!{"ann", "-6"}, vec!{"ben", "4"}, vec!{"cat", "5"}, vec!{"dan", "4"}, vec!{"eve", "-5"}}),
+	("SELECT team, SUM(points), RANK() OVER (ORDER BY SUM(points) DESC) FROM scores GROUP BY team;", vec!{vec!{"red", "50", "1"}, vec!{"blue", "20", "2"}}),
+	("SELECT name, ROW_NUMBER() OVER (ORDER BY name) FROM scores WHERE points > 10 LIMIT 2;", vec!{vec!{"ben", "1"}, vec!{"cat", "2"}}),
+	("SELECT name FROM scores ORDER BY ROW_NUMBER() OVER (ORDER BY points DESC, name) LIMIT 2;", vec!{vec!{"ben"}, vec!{"dan"}}),
+	("SELECT DISTINCT team, COUNT(*) OVER (PARTITION BY team) FROM scores ORDER BY team;", vec!{vec!{"blue", "2"}, vec!{"red", "3"}}),
+})]
+fn window_functions(case: (&str, Vec<Vec<&str>>)) {
+	let (source, rows) = case;
+	let mut backend = MemoryBackend::new();
+	execute(&mut backend, "CREATE TABLE scores (name TEXT, team TEXT, points INT);
+		INSERT INTO scores VALUES ('ann', 'red', 10), ('ben', 'red', 20), ('cat', 'blue', 15), ('dan', 'red', 20), ('eve', 'blue', 5);").unwrap();
+	let results = execute(&mut backend, source).unwrap().unwrap();
+	assert_eq!(render(&results), rows);
+}
@@ -2,6 +2,7 @@ use parameterized::parameterized;
 use rustsql::parser::*;
 use rustsql::ast::*;
 use rustsql::lexer::*;
+use rustsql::error::Error;
 
 #[parameterized(case = {
 	("CREATE TABLE users (id INT, name TEXT);",
@@ -9,6 +10,10 @@ use rustsql::lexer::*;
 			statements: vec!{
 				Statement{
 					kind: AstKind::CreateTableKind,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
 					insert_statement: None,
 					select_statement: None,
 					create_table_statement: Some(CreateTableStatement{
@@ -28,7 +33,58 @@ use rustsql::lexer::*;
 									loc: Location{col: 23, line: 0},
 									kind: TokenKind::KeywordKind,
 									value: "int".to_string()
-								}
+								},
+								not_null: false
+							},
+							ColumnDefinition{
+								name: Token{
+									loc:   Location{col: 28, line: 0},
+									kind:  TokenKind::IdentifierKind,
+									value: "name".to_string(),
+								},
+								datatype: Token{
+									loc:   Location{col: 33, line: 0},
+									kind:  TokenKind::KeywordKind,
+									value: "text".to_string(),
+								},
+								not_null: false
+							}
+						},
+						if_not_exists: false
+					})
+				}
+			}
+		}),
+	("CREATE TABLE users (id INT, name TEXT NOT NULL);",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::CreateTableKind,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					insert_statement: None,
+					select_statement: None,
+					create_table_statement: Some(CreateTableStatement{
+						name: Token{
+							loc: Location{col: 13, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "users".to_string()
+						},
+						cols: vec!{
+							ColumnDefinition{
+								name: Token{
+									loc: Location{col: 20, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "id".to_string()
+								},
+								datatype: Token{
+									loc: Location{col: 23, line: 0},
+									kind: TokenKind::KeywordKind,
+									value: "int".to_string()
+								},
+								not_null: false
 							},
 							ColumnDefinition{
 								name: Token{
@@ -40,6 +96,318 @@ use rustsql::lexer::*;
 									loc:   Location{col: 33, line: 0},
 									kind:  TokenKind::KeywordKind,
 									value: "text".to_string(),
+								},
+								not_null: true
+							}
+						},
+						if_not_exists: false
+					})
+				}
+			}
+		}),
+	("SELECT id FROM users WHERE id = 1;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: Some(Token{
+											loc: Location{col: 7, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: None
+								}
+							},
+							from: Some(FromItem{
+								table: Some(Token{
+									loc: Location{col: 15, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "users".to_string()
+								}),
+								alias: None,
+								join: None,
+								subquery: None,
+								kind: FromItemKind::TableKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: Some(Expression{
+								literal: None,
+								table: None,
+								binary: Some(BinaryExpression{
+									a: Box::new(Expression{
+										literal: Some(Token{
+											loc: Location{col: 27, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									b: Box::new(Expression{
+										literal: Some(Token{
+											loc: Location{col: 32, line: 0},
+											kind: TokenKind::NumericKind,
+											value: "1".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									op: Token{
+										loc: Location{col: 30, line: 0},
+										kind: TokenKind::SymbolKind,
+										value: "=".to_string()
+									}
+								}),
+								unary: None,
+								function: None,
+								subquery: None,
+								kind: ExpressionKind::BinaryKind
+							})
+						}
+					})
+				}
+			}
+		}),
+	("SELECT *, users.* FROM users;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: None,
+									asterisk: true,
+									table: None,
+									as_name: None
+								},
+								SelectItem{
+									exp: None,
+									asterisk: true,
+									table: Some(Token{
+									loc: Location{col: 10, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "users".to_string()
+									}),
+									as_name: None
+								}
+							},
+							from: Some(FromItem{
+								table: Some(Token{
+									loc: Location{col: 23, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "users".to_string()
+								}),
+								alias: None,
+								join: None,
+								subquery: None,
+								kind: FromItemKind::TableKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
+	("SELECT id AS user_id FROM users;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: Some(Token{
+											loc: Location{col: 7, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: Some(Token{
+										loc: Location{col: 13, line: 0},
+										kind: TokenKind::IdentifierKind,
+										value: "user_id".to_string()
+									})
+								}
+							},
+							from: Some(FromItem{
+								table: Some(Token{
+									loc: Location{col: 26, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "users".to_string()
+								}),
+								alias: None,
+								join: None,
+								subquery: None,
+								kind: FromItemKind::TableKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
+	("INSERT INTO users (name, id) VALUES ('a', 1), ('b', 2);",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::InsertKind,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					select_statement: None,
+					create_table_statement: None,
+					insert_statement: Some(InsertStatement{
+						table: Token{
+							loc: Location{col: 12, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "users".to_string()
+						},
+						columns: Some(vec!{
+							Token{
+								loc: Location{col: 19, line: 0},
+								kind: TokenKind::IdentifierKind,
+								value: "name".to_string()
+							},
+							Token{
+								loc: Location{col: 25, line: 0},
+								kind: TokenKind::IdentifierKind,
+								value: "id".to_string()
+							}
+						}),
+						values: vec!{
+							vec!{
+								Expression{
+									literal: Some(Token{
+										loc: Location{col: 37, line: 0},
+										kind: TokenKind::StringKind,
+										value: "a".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
+								},
+								Expression{
+									literal: Some(Token{
+										loc: Location{col: 42, line: 0},
+										kind: TokenKind::NumericKind,
+										value: "1".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
+								}
+							},
+							vec!{
+								Expression{
+									literal: Some(Token{
+										loc: Location{col: 47, line: 0},
+										kind: TokenKind::StringKind,
+										value: "b".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
+								},
+								Expression{
+									literal: Some(Token{
+										loc: Location{col: 52, line: 0},
+										kind: TokenKind::NumericKind,
+										value: "2".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
 								}
 							}
 						}
@@ -47,11 +415,964 @@ use rustsql::lexer::*;
 				}
 			}
 		}),
-
+	("UPDATE users SET name = 'x', id = id + 1 WHERE id = 1;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::UpdateKind,
+					select_statement: None,
+					create_table_statement: None,
+					insert_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					update_statement: Some(UpdateStatement{
+						table: Token{
+							loc: Location{col: 7, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "users".to_string()
+						},
+						set: vec!{
+							Assignment{
+								column: Token{
+									loc: Location{col: 17, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "name".to_string()
+								},
+								value: Expression{
+									literal: Some(Token{
+										loc: Location{col: 24, line: 0},
+										kind: TokenKind::StringKind,
+										value: "x".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
+								}
+							},
+							Assignment{
+								column: Token{
+									loc: Location{col: 29, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "id".to_string()
+								},
+								value: Expression{
+									literal: None,
+									table: None,
+									binary: Some(BinaryExpression{
+										a: Box::new(Expression{
+											literal: Some(Token{
+												loc: Location{col: 34, line: 0},
+												kind: TokenKind::IdentifierKind,
+												value: "id".to_string()
+											}),
+											table: None,
+											binary: None,
+											unary: None,
+											function: None,
+											subquery: None,
+											kind: ExpressionKind::LiteralKind
+										}),
+										b: Box::new(Expression{
+											literal: Some(Token{
+												loc: Location{col: 39, line: 0},
+												kind: TokenKind::NumericKind,
+												value: "1".to_string()
+											}),
+											table: None,
+											binary: None,
+											unary: None,
+											function: None,
+											subquery: None,
+											kind: ExpressionKind::LiteralKind
+										}),
+										op: Token{
+											loc: Location{col: 37, line: 0},
+											kind: TokenKind::SymbolKind,
+											value: "+".to_string()
+										}
+									}),
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::BinaryKind
+								}
+							}
+						},
+						filter: Some(Expression{
+							literal: None,
+							table: None,
+							binary: Some(BinaryExpression{
+								a: Box::new(Expression{
+									literal: Some(Token{
+										loc: Location{col: 47, line: 0},
+										kind: TokenKind::IdentifierKind,
+										value: "id".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
+								}),
+								b: Box::new(Expression{
+									literal: Some(Token{
+										loc: Location{col: 52, line: 0},
+										kind: TokenKind::NumericKind,
+										value: "1".to_string()
+									}),
+									table: None,
+									binary: None,
+									unary: None,
+									function: None,
+									subquery: None,
+									kind: ExpressionKind::LiteralKind
+								}),
+								op: Token{
+									loc: Location{col: 50, line: 0},
+									kind: TokenKind::SymbolKind,
+									value: "=".to_string()
+								}
+							}),
+							unary: None,
+							function: None,
+							subquery: None,
+							kind: ExpressionKind::BinaryKind
+						})
+					})
+				}
+			}
+		}),
+	("DELETE FROM users;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::DeleteKind,
+					select_statement: None,
+					create_table_statement: None,
+					insert_statement: None,
+					update_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: None,
+					delete_statement: Some(DeleteStatement{
+						table: Token{
+							loc: Location{col: 12, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "users".to_string()
+						},
+						filter: None
+					})
+				}
+			}
+		}),
+	("DROP TABLE IF EXISTS users;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::DropTableKind,
+					select_statement: None,
+					create_table_statement: None,
+					insert_statement: None,
+					update_statement: None,
+					delete_statement: None,
+					alter_table_statement: None,
+					drop_table_statement: Some(DropTableStatement{
+						name: Token{
+							loc: Location{col: 21, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "users".to_string()
+						},
+						if_exists: true
+					})
+				}
+			}
+		}),
+	("ALTER TABLE users RENAME COLUMN name TO full_name;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::AlterTableKind,
+					select_statement: None,
+					create_table_statement: None,
+					insert_statement: None,
+					update_statement: None,
+					delete_statement: None,
+					drop_table_statement: None,
+					alter_table_statement: Some(AlterTableStatement{
+						table: Token{
+							loc: Location{col: 12, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "users".to_string()
+						},
+						column_definition: None,
+						column: Some(Token{
+							loc: Location{col: 32, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "name".to_string()
+						}),
+						new_name: Some(Token{
+							loc: Location{col: 40, line: 0},
+							kind: TokenKind::IdentifierKind,
+							value: "full_name".to_string()
+						}),
+						kind: AlterTableKind::RenameColumnKind
+					})
+				}
+			}
+		}),
+	("SELECT id FROM users ORDER BY name DESC, id NULLS FIRST;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					alter_table_statement: None,
+					drop_table_statement: None,
+					delete_statement: None,
+					update_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: Some(Token{
+											loc: Location{col: 7, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: None
+								}
+							},
+							from: Some(FromItem{
+								table: Some(Token{
+									loc: Location{col: 15, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "users".to_string()
+								}),
+								alias: None,
+								join: None,
+								subquery: None,
+								kind: FromItemKind::TableKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{
+								OrderByItem{
+									exp: Expression{
+										literal: Some(Token{
+											loc: Location{col: 30, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "name".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									},
+									desc: true,
+									nulls_first: true
+								},
+								OrderByItem{
+									exp: Expression{
+										literal: Some(Token{
+											loc: Location{col: 41, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									},
+									desc: false,
+									nulls_first: true
+								}
+							},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
+	("SELECT u.id FROM users u LEFT JOIN orders ON u.id = user_id;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					alter_table_statement: None,
+					drop_table_statement: None,
+					delete_statement: None,
+					update_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: Some(Token{
+											loc: Location{col: 9, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: Some(Token{
+										loc: Location{col: 7, line: 0},
+										kind: TokenKind::IdentifierKind,
+										value: "u".to_string()
+									}),
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: None
+								}
+							},
+							from: Some(FromItem{
+								table: None,
+								alias: None,
+								join: Some(JoinItem{
+									left: Box::new(FromItem{
+										table: Some(Token{
+											loc: Location{col: 17, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "users".to_string()
+										}),
+										alias: Some(Token{
+											loc: Location{col: 23, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "u".to_string()
+										}),
+										join: None,
+										subquery: None,
+										kind: FromItemKind::TableKind
+									}),
+									right: Box::new(FromItem{
+										table: Some(Token{
+											loc: Location{col: 35, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "orders".to_string()
+										}),
+										alias: None,
+										join: None,
+										subquery: None,
+										kind: FromItemKind::TableKind
+									}),
+									join_type: JoinType::LeftJoin,
+									on: Some(Expression{
+										literal: None,
+										table: None,
+										binary: Some(BinaryExpression{
+											a: Box::new(Expression{
+												literal: Some(Token{
+													loc: Location{col: 47, line: 0},
+													kind: TokenKind::IdentifierKind,
+													value: "id".to_string()
+												}),
+												table: Some(Token{
+												loc: Location{col: 45, line: 0},
+												kind: TokenKind::IdentifierKind,
+												value: "u".to_string()
+											}),
+												binary: None,
+												unary: None,
+												function: None,
+												subquery: None,
+												kind: ExpressionKind::LiteralKind
+											}),
+											b: Box::new(Expression{
+												literal: Some(Token{
+													loc: Location{col: 52, line: 0},
+													kind: TokenKind::IdentifierKind,
+													value: "user_id".to_string()
+												}),
+												table: None,
+												binary: None,
+												unary: None,
+												function: None,
+												subquery: None,
+												kind: ExpressionKind::LiteralKind
+											}),
+											op: Token{
+												loc: Location{col: 50, line: 0},
+												kind: TokenKind::SymbolKind,
+												value: "=".to_string()
+											}
+										}),
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::BinaryKind
+									})
+								}),
+								subquery: None,
+								kind: FromItemKind::JoinKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
+	("SELECT id FROM users WHERE id NOT IN (SELECT 1);",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					alter_table_statement: None,
+					drop_table_statement: None,
+					delete_statement: None,
+					update_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: Some(Token{
+											loc: Location{col: 7, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "id".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: None
+								}
+							},
+							from: Some(FromItem{
+								table: Some(Token{
+									loc: Location{col: 15, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "users".to_string()
+								}),
+								alias: None,
+								join: None,
+								subquery: None,
+								kind: FromItemKind::TableKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: Some(Expression{
+								literal: None,
+								table: None,
+								binary: None,
+								unary: Some(UnaryExpression{
+									operand: Box::new(Expression{
+										literal: None,
+										table: None,
+										binary: Some(BinaryExpression{
+											a: Box::new(Expression{
+												literal: Some(Token{
+													loc: Location{col: 27, line: 0},
+													kind: TokenKind::IdentifierKind,
+													value: "id".to_string()
+												}),
+												table: None,
+												binary: None,
+												unary: None,
+												function: None,
+												subquery: None,
+												kind: ExpressionKind::LiteralKind
+											}),
+											b: Box::new(Expression{
+											literal: None,
+											table: None,
+											binary: None,
+											unary: None,
+											function: None,
+											subquery: Some(Box::new(SelectStatement{
+												distinct: false,
+												item: vec!{
+													SelectItem{
+														exp: Some(Expression{
+															literal: Some(Token{
+																loc: Location{col: 45, line: 0},
+																kind: TokenKind::NumericKind,
+																value: "1".to_string()
+															}),
+															table: None,
+															binary: None,
+															unary: None,
+															function: None,
+															subquery: None,
+															kind: ExpressionKind::LiteralKind
+														}),
+														asterisk: false,
+														table: None,
+														as_name: None
+													}
+												},
+												from: None,
+												limit: None,
+												offset: None,
+												set_operation: None,
+												group_by: vec!{},
+												having: None,
+												order_by: vec!{},
+												filter: None
+											})),
+											kind: ExpressionKind::SubqueryKind
+										}),
+											op: Token{
+												loc: Location{col: 34, line: 0},
+												kind: TokenKind::KeywordKind,
+												value: "in".to_string()
+											}
+										}),
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::BinaryKind
+									}),
+									op: Token{
+										loc: Location{col: 30, line: 0},
+										kind: TokenKind::KeywordKind,
+										value: "not".to_string()
+									}
+								}),
+								function: None,
+								subquery: None,
+								kind: ExpressionKind::UnaryKind
+							})
+						}
+					})
+				}
+			}
+		}),
+	("SELECT 1 UNION ALL SELECT 2 LIMIT 1;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					alter_table_statement: None,
+					drop_table_statement: None,
+					delete_statement: None,
+					update_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{},
+							from: None,
+							limit: Some(Expression{
+								literal: Some(Token{
+									loc: Location{col: 34, line: 0},
+									kind: TokenKind::NumericKind,
+									value: "1".to_string()
+								}),
+								table: None,
+								binary: None,
+								unary: None,
+								function: None,
+								subquery: None,
+								kind: ExpressionKind::LiteralKind
+							}),
+							offset: None,
+							set_operation: Some(SetOperation{
+								left: Box::new(SelectStatement{
+									distinct: false,
+									item: vec!{
+										SelectItem{
+											exp: Some(Expression{
+												literal: Some(Token{
+													loc: Location{col: 7, line: 0},
+													kind: TokenKind::NumericKind,
+													value: "1".to_string()
+												}),
+												table: None,
+												binary: None,
+												unary: None,
+												function: None,
+												subquery: None,
+												kind: ExpressionKind::LiteralKind
+											}),
+											asterisk: false,
+											table: None,
+											as_name: None
+										}
+									},
+									from: None,
+									limit: None,
+									offset: None,
+									set_operation: None,
+									group_by: vec!{},
+									having: None,
+									order_by: vec!{},
+									filter: None
+								}),
+								right: Box::new(SelectStatement{
+									distinct: false,
+									item: vec!{
+										SelectItem{
+											exp: Some(Expression{
+												literal: Some(Token{
+													loc: Location{col: 26, line: 0},
+													kind: TokenKind::NumericKind,
+													value: "2".to_string()
+												}),
+												table: None,
+												binary: None,
+												unary: None,
+												function: None,
+												subquery: None,
+												kind: ExpressionKind::LiteralKind
+											}),
+											asterisk: false,
+											table: None,
+											as_name: None
+										}
+									},
+									from: None,
+									limit: None,
+									offset: None,
+									set_operation: None,
+									group_by: vec!{},
+									having: None,
+									order_by: vec!{},
+									filter: None
+								}),
+								op: SetOperator::UnionOperator,
+								all: true
+							}),
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
+	("WITH RECURSIVE t (n) AS (SELECT 1) SELECT n FROM t;",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					alter_table_statement: None,
+					drop_table_statement: None,
+					delete_statement: None,
+					update_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: true,
+						with: vec!{
+							CommonTableExpression{
+								name: Token{
+									loc: Location{col: 15, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "t".to_string()
+								},
+								columns: vec!{
+									Token{
+										loc: Location{col: 18, line: 0},
+										kind: TokenKind::IdentifierKind,
+										value: "n".to_string()
+									}
+								},
+								query: SelectStatement{
+									distinct: false,
+									item: vec!{
+										SelectItem{
+											exp: Some(Expression{
+												literal: Some(Token{
+													loc: Location{col: 32, line: 0},
+													kind: TokenKind::NumericKind,
+													value: "1".to_string()
+												}),
+												table: None,
+												binary: None,
+												unary: None,
+												function: None,
+												subquery: None,
+												kind: ExpressionKind::LiteralKind
+											}),
+											asterisk: false,
+											table: None,
+											as_name: None
+										}
+									},
+									from: None,
+									limit: None,
+									offset: None,
+									set_operation: None,
+									group_by: vec!{},
+									having: None,
+									order_by: vec!{},
+									filter: None
+								}
+							}
+						},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: Some(Token{
+											loc: Location{col: 42, line: 0},
+											kind: TokenKind::IdentifierKind,
+											value: "n".to_string()
+										}),
+										table: None,
+										binary: None,
+										unary: None,
+										function: None,
+										subquery: None,
+										kind: ExpressionKind::LiteralKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: None
+								}
+							},
+							from: Some(FromItem{
+								table: Some(Token{
+									loc: Location{col: 49, line: 0},
+									kind: TokenKind::IdentifierKind,
+									value: "t".to_string()
+								}),
+								alias: None,
+								join: None,
+								subquery: None,
+								kind: FromItemKind::TableKind
+							}),
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
+	("SELECT rank() OVER (PARTITION BY a ORDER BY b ROWS BETWEEN 1 PRECEDING AND CURRENT ROW);",
+		Ast{
+			statements: vec!{
+				Statement{
+					kind: AstKind::SelectKind,
+					alter_table_statement: None,
+					drop_table_statement: None,
+					delete_statement: None,
+					update_statement: None,
+					insert_statement: None,
+					create_table_statement: None,
+					select_statement: Some(QueryStatement{
+						recursive: false,
+						with: vec!{},
+						select: SelectStatement{
+							distinct: false,
+							item: vec!{
+								SelectItem{
+									exp: Some(Expression{
+										literal: None,
+										table: None,
+										binary: None,
+										unary: None,
+										function: Some(FunctionExpression{
+											name: Token{
+												loc: Location{col: 7, line: 0},
+												kind: TokenKind::IdentifierKind,
+												value: "rank".to_string()
+											},
+											distinct: false,
+											args: vec!{},
+											asterisk: false,
+											over: Some(WindowSpecification{
+												partition_by: vec!{
+													Expression{
+														literal: Some(Token{
+															loc: Location{col: 33, line: 0},
+															kind: TokenKind::IdentifierKind,
+															value: "a".to_string()
+														}),
+														table: None,
+														binary: None,
+														unary: None,
+														function: None,
+														subquery: None,
+														kind: ExpressionKind::LiteralKind
+													}
+												},
+												order_by: vec!{
+													OrderByItem{
+														exp: Expression{
+															literal: Some(Token{
+																loc: Location{col: 44, line: 0},
+																kind: TokenKind::IdentifierKind,
+																value: "b".to_string()
+															}),
+															table: None,
+															binary: None,
+															unary: None,
+															function: None,
+															subquery: None,
+															kind: ExpressionKind::LiteralKind
+														},
+														desc: false,
+														nulls_first: false
+													}
+												},
+												frame: Some(WindowFrame{
+													units: FrameUnits::RowsUnits,
+													start: FrameBound{
+														offset: Some(Box::new(Expression{
+															literal: Some(Token{
+																loc: Location{col: 59, line: 0},
+																kind: TokenKind::NumericKind,
+																value: "1".to_string()
+															}),
+															table: None,
+															binary: None,
+															unary: None,
+															function: None,
+															subquery: None,
+															kind: ExpressionKind::LiteralKind
+														})),
+														kind: FrameBoundKind::PrecedingKind
+													},
+													end: FrameBound{
+														offset: None,
+														kind: FrameBoundKind::CurrentRowKind
+													}
+												})
+											})
+										}),
+										subquery: None,
+										kind: ExpressionKind::FunctionKind
+									}),
+									asterisk: false,
+									table: None,
+									as_name: None
+								}
+							},
+							from: None,
+							limit: None,
+							offset: None,
+							set_operation: None,
+							group_by: vec!{},
+							having: None,
+							order_by: vec!{},
+							filter: None
+						}
+					})
+				}
+			}
+		}),
 })]
 fn parse(case: (&str, Ast)){
 	let (source, ast) = case;
 	println!("(Parser) Testing: {}", source);
 	let output = parse(source).unwrap();
 	assert_eq!(ast, output);
-}
\ No newline at end of file
+}
+
+#[parameterized(case = {
+	("SELECT id FROM;", Location{col: 14, line: 0}),
+	("SELECT id, FROM users;", Location{col: 11, line: 0}),
+	("INSERT users VALUES (1);", Location{col: 7, line: 0}),
+	("INSERT INTO users (id VALUES (1);", Location{col: 22, line: 0}),
+	("INSERT INTO users VALUES (1) (2);", Location{col: 29, line: 0}),
+	("SELECT 1 SELECT 2;", Location{col: 9, line: 0}),
+	("SELECT 'a", Location{col: 7, line: 0}),
+	("SELECT id IS 1 FROM users;", Location{col: 13, line: 0}),
+	("CREATE TABLE t (a INT NOT);", Location{col: 25, line: 0}),
+	("UPDATE users name = 'x';", Location{col: 13, line: 0}),
+	("UPDATE users SET name 'x';", Location{col: 22, line: 0}),
+	("DELETE users;", Location{col: 7, line: 0}),
+	("CREATE TABLE IF EXISTS t (a INT);", Location{col: 16, line: 0}),
+	("DROP TABLE IF users;", Location{col: 14, line: 0}),
+	("ALTER TABLE users ALTER COLUMN id;", Location{col: 18, line: 0}),
+	("ALTER TABLE users RENAME COLUMN TO b;", Location{col: 32, line: 0}),
+	("ALTER TABLE users ADD COLUMN age;", Location{col: 32, line: 0}),
+	("SELECT id FROM users ORDER id;", Location{col: 27, line: 0}),
+	("SELECT id FROM users ORDER BY id NULLS;", Location{col: 38, line: 0}),
+	("SELECT id FROM users LIMIT;", Location{col: 26, line: 0}),
+	("SELECT id FROM users OFFSET 1 LIMIT 1;", Location{col: 30, line: 0}),
+	("SELECT count(id FROM users;", Location{col: 16, line: 0}),
+	("SELECT id FROM users GROUP id;", Location{col: 27, line: 0}),
+	("SELECT id FROM users GROUP BY;", Location{col: 29, line: 0}),
+	("SELECT id FROM users GROUP BY id name;", Location{col: 33, line: 0}),
+	("SELECT count(DISTINCT *) FROM users;", Location{col: 22, line: 0}),
+	("SELECT id FROM users JOIN orders;", Location{col: 32, line: 0}),
+	("SELECT id FROM users LEFT orders ON true;", Location{col: 26, line: 0}),
+	("SELECT id FROM users, JOIN orders;", Location{col: 22, line: 0}),
+	("SELECT u. FROM users u;", Location{col: 10, line: 0}),
+	("SELECT id FROM (SELECT 1);", Location{col: 25, line: 0}),
+	("SELECT id FROM users WHERE id IN users;", Location{col: 33, line: 0}),
+	("SELECT EXISTS users;", Location{col: 14, line: 0}),
+	("SELECT (SELECT 1;", Location{col: 16, line: 0}),
+	("SELECT 1 LIMIT 1 UNION SELECT 2;", Location{col: 17, line: 0}),
+	("SELECT 1 UNION;", Location{col: 14, line: 0}),
+	("WITH t AS SELECT 1 SELECT 1;", Location{col: 10, line: 0}),
+	("WITH t (n AS (SELECT 1) SELECT n FROM t;", Location{col: 10, line: 0}),
+	("WITH t AS (SELECT 1);", Location{col: 20, line: 0}),
+	("SELECT rank() OVER;", Location{col: 18, line: 0}),
+	("SELECT rank() OVER (PARTITION a);", Location{col: 30, line: 0}),
+	("SELECT sum(a) OVER (ROWS UNBOUNDED FOLLOWING);", Location{col: 25, line: 0}),
+	("SELECT sum(a) OVER (ROWS BETWEEN 1 PRECEDING CURRENT ROW);", Location{col: 45, line: 0}),
+	("SELECT sum(a) OVER (ROWS 1);", Location{col: 26, line: 0}),
+})]
+fn parse_error(case: (&str, Location)){
+	let (source, loc) = case;
+	match parse(source) {
+		Err(Error::ParseError{loc: err_loc, ..}) | Err(Error::LexError{loc: err_loc, ..}) => assert_eq!(loc, err_loc),
+		other => panic!("Expected error, got {:?}", other)
+	}
+}